@@ -0,0 +1,27 @@
+/// Quotes a field if it contains a separator, quote or line break.
+pub fn field(value: &str) -> String {
+    if value.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Joins the quoted fields of a row, without the line break.
+pub fn row<'a, I: IntoIterator<Item = &'a str>>(values: I) -> String {
+    values.into_iter().map(field).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod test {
+    use super::row;
+
+    #[test]
+    fn quoting() {
+        assert_eq!(
+            r#"a,"b,c","say ""hi""","x
+y""#,
+            row(vec!["a", "b,c", "say \"hi\"", "x\ny"])
+        );
+    }
+}
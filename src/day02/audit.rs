@@ -0,0 +1,314 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    fs::File,
+    io::{
+        BufRead,
+        BufReader,
+        Write,
+    },
+    path::PathBuf,
+};
+
+use rayon::prelude::*;
+use thiserror::Error;
+
+use super::{
+    password_policy::{
+        PasswordPolicy,
+        Registry,
+    },
+    tester,
+};
+use crate::csv;
+
+const DEFAULT_KINDS: [&str; 2] = ["sled", "toboggan"];
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("can not read {0:?}")]
+    Read(String, #[source] std::io::Error),
+
+    #[error("can not write report")]
+    Write(#[from] std::io::Error),
+
+    #[error("unknown policy kind {0:?}, expected one of {1}")]
+    UnknownKind(String, String),
+
+    #[error("missing value for {0}")]
+    MissingValue(String),
+}
+
+/// A "policy: password" line and where it was read from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Line {
+    pub source: String,
+
+    /// Line number, starting at 1.
+    pub number: usize,
+
+    pub text: String,
+}
+
+/// A line rejected by one policy kind.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Failure {
+    pub source: String,
+    pub line: usize,
+    pub kind: String,
+    pub entry: String,
+    pub reason: String,
+}
+
+/// A line is valid if it is valid under every selected policy kind.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Report {
+    pub total: usize,
+    pub valid: usize,
+    pub invalid: usize,
+    pub failures: Vec<Failure>,
+}
+
+/// Reads all non blank lines, `source` is used to refer to them in the
+/// report.
+pub fn read<R: BufRead>(source: &str, reader: R) -> Result<Vec<Line>, Error> {
+    let mut lines = Vec::new();
+
+    for (index, text) in reader.lines().enumerate() {
+        let text = text.map_err(|e| Error::Read(source.to_string(), e))?;
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        lines.push(Line {
+            source: source.to_string(),
+            number: index + 1,
+            text,
+        });
+    }
+
+    Ok(lines)
+}
+
+fn failure(line: &Line, kind: &str, reason: String) -> Failure {
+    Failure {
+        source: line.source.clone(),
+        line: line.number,
+        kind: kind.to_string(),
+        entry: line.text.clone(),
+        reason,
+    }
+}
+
+/// Parsed policies by kind and policy text, invalid policies keep their error.
+type Policies<'a> = HashMap<(&'a str, &'a str), Result<Box<dyn PasswordPolicy>, String>>;
+
+fn check(policies: &Policies<'_>, kinds: &[String], line: &Line) -> Vec<Failure> {
+    let (policy, password) = match tester::split(&line.text) {
+        Ok(split) => split,
+        Err(e) => return vec![failure(line, "", e.to_string())],
+    };
+
+    kinds
+        .iter()
+        .filter_map(|kind| match &policies[&(kind.as_str(), policy)] {
+            Ok(policy) => {
+                let verdict = policy.check(password);
+
+                if verdict.is_valid() {
+                    None
+                } else {
+                    Some(failure(line, kind, verdict.to_string()))
+                }
+            }
+            Err(e) => Some(failure(line, kind, e.clone())),
+        })
+        .collect()
+}
+
+/// Checks all lines in parallel under each of the policy kinds.
+pub fn audit(registry: &Registry, kinds: &[String], lines: &[Line]) -> Result<Report, Error> {
+    if let Some(kind) = kinds
+        .iter()
+        .find(|kind| !registry.kinds().any(|k| k == *kind))
+    {
+        let known = registry.kinds().collect::<Vec<_>>().join(", ");
+
+        return Err(Error::UnknownKind(kind.clone(), known));
+    }
+
+    // Every distinct policy is only parsed once per kind
+    let distinct = lines
+        .iter()
+        .filter_map(|line| tester::split(&line.text).ok())
+        .map(|(policy, _)| policy)
+        .collect::<HashSet<_>>();
+    let policies = kinds
+        .iter()
+        .flat_map(|kind| distinct.iter().map(move |policy| (kind.as_str(), *policy)))
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|(kind, policy)| {
+            let parsed = registry.parse(kind, policy).map_err(|e| e.to_string());
+            ((kind, policy), parsed)
+        })
+        .collect::<Policies<'_>>();
+
+    let failures = lines
+        .par_iter()
+        .map(|line| check(&policies, kinds, line))
+        .collect::<Vec<_>>();
+
+    let invalid = failures.iter().filter(|f| !f.is_empty()).count();
+
+    Ok(Report {
+        total: lines.len(),
+        valid: lines.len() - invalid,
+        invalid,
+        failures: failures.into_iter().flatten().collect(),
+    })
+}
+
+impl Report {
+    /// Writes the failures, one row per line and policy kind.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "source,line,kind,entry,reason")?;
+
+        for failure in &self.failures {
+            let line = failure.line.to_string();
+            let row = csv::row(vec![
+                failure.source.as_str(),
+                line.as_str(),
+                failure.kind.as_str(),
+                failure.entry.as_str(),
+                failure.reason.as_str(),
+            ]);
+
+            writeln!(writer, "{}", row)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "total   {}", self.total)?;
+        writeln!(f, "valid   {}", self.valid)?;
+        writeln!(f, "invalid {}", self.invalid)
+    }
+}
+
+/// Runs an audit from command line arguments:
+/// `[--kind <kind>]... [<file>|-]...`. Reads stdin without files and checks
+/// sled and toboggan policies without kinds. The counts are written to
+/// stderr, the failures as CSV to stdout.
+pub fn run<I: Iterator<Item = String>>(mut args: I) -> Result<(), Error> {
+    let mut kinds = Vec::new();
+    let mut paths = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if arg == "--kind" {
+            kinds.push(args.next().ok_or(Error::MissingValue(arg))?);
+        } else {
+            paths.push(arg);
+        }
+    }
+
+    if kinds.is_empty() {
+        kinds = DEFAULT_KINDS.iter().map(ToString::to_string).collect();
+    }
+    if paths.is_empty() {
+        paths.push("-".to_string());
+    }
+
+    let mut lines = Vec::new();
+    for path in &paths {
+        if path == "-" {
+            lines.extend(read("-", std::io::stdin().lock())?);
+        } else {
+            let file = File::open(PathBuf::from(path)).map_err(|e| Error::Read(path.clone(), e))?;
+            lines.extend(read(path, BufReader::new(file))?);
+        }
+    }
+
+    let report = audit(&Registry::default(), &kinds, &lines)?;
+
+    eprint!("{}", report);
+    report.write_csv(std::io::stdout().lock())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        audit,
+        read,
+        Error,
+        Line,
+        Registry,
+    };
+
+    fn lines(input: &[&str]) -> Vec<Line> {
+        read("test", input.join("\n").as_bytes()).expect("can not read input")
+    }
+
+    fn kinds(kinds: &[&str]) -> Vec<String> {
+        kinds.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn input() {
+        let input = lines(&super::super::INPUT);
+        let registry = Registry::default();
+
+        let sled = audit(&registry, &kinds(&["sled"]), &input).expect("can not audit");
+        let toboggan = audit(&registry, &kinds(&["toboggan"]), &input).expect("can not audit");
+
+        assert_eq!((1000, 591), (sled.total, sled.valid));
+        assert_eq!((1000, 335), (toboggan.total, toboggan.valid));
+    }
+
+    #[test]
+    fn csv() {
+        let input = lines(&[
+            "1-3 a: abcde",
+            "",
+            "1-3 b: cdefg",
+            "2-9 c: ccccccccc",
+            "2-9 c",
+        ]);
+        let report = audit(&Registry::default(), &kinds(&["sled", "toboggan"]), &input)
+            .expect("can not audit");
+
+        assert_eq!((4, 1, 3), (report.total, report.valid, report.invalid));
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).expect("can not write csv");
+        let csv = String::from_utf8(csv).unwrap();
+
+        assert_eq!(
+            vec![
+                "source,line,kind,entry,reason",
+                "test,3,sled,1-3 b: cdefg,\"'b' occurs 0 times, allowed 1-3\"",
+                "test,3,toboggan,1-3 b: cdefg,'b' at neither position 1 nor 3",
+                "test,4,toboggan,2-9 c: ccccccccc,'c' at both positions 2 and 9",
+                r#"test,5,,2-9 c,"invalid entry ""2-9 c"": expected ""policy: password""""#,
+            ],
+            csv.lines().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn unknown_kind() {
+        let input = lines(&["1-3 a: abcde"]);
+
+        assert!(matches!(
+            audit(&Registry::default(), &kinds(&["sled", "bogus"]), &input),
+            Err(Error::UnknownKind(kind, _)) if kind == "bogus"
+        ));
+    }
+}
@@ -1,10 +1,14 @@
 use thiserror::Error;
 
+#[allow(dead_code)]
+pub(crate) mod audit;
 mod input;
 mod password_policy;
 mod tester;
 
 use input::INPUT;
+#[cfg(test)]
+use password_policy::PasswordPolicy;
 use password_policy::{
     sled_rental::PasswordPolicy as PasswordPolicySledRental,
     toboggan_rental::PasswordPolicy as PasswordPolicyTobogganRental,
@@ -34,6 +38,7 @@ const TEST_INPUT_TOBOGGAN_RENTAL: [(&str, Result<bool, Error>); 4] = [
 
 #[derive(Debug, Error, Eq, PartialEq)]
 pub enum Error {
+    #[cfg(test)]
     #[error("missing policy in input")]
     MissingPolicy,
 
@@ -45,6 +50,10 @@ pub enum Error {
     #[error("invalid sled policy: {0}")]
     InvalidTobogganPolicy(password_policy::toboggan_rental::Error),
 
+    #[error("invalid entry {entry:?}: {reason}")]
+    InvalidEntry { entry: String, reason: String },
+
+    #[cfg(test)]
     #[error("missing password in input")]
     MissingPassword,
 }
@@ -214,7 +223,6 @@ mod bench {
             super::INPUT
                 .iter()
                 .filter_map(|input| tester.test(input).ok())
-                .into_iter()
                 .filter(|is_valid| *is_valid)
                 .count();
         });
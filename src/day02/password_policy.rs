@@ -1,4 +1,193 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    ops::RangeInclusive,
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// Checks a password against a single parsed policy.
+pub trait PasswordPolicy: std::fmt::Debug + Send + Sync {
+    fn check(&self, password: &str) -> Verdict;
+
+    #[allow(dead_code)]
+    fn is_valid_password(&self, password: &str) -> bool {
+        self.check(password).is_valid()
+    }
+}
+
+impl<P: PasswordPolicy + ?Sized> PasswordPolicy for Box<P> {
+    fn check(&self, password: &str) -> Verdict {
+        (**self).check(password)
+    }
+}
+
+/// A position of a toboggan policy, `at` starts at 1, and what was found
+/// there.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Position {
+    pub at: usize,
+    pub matches: bool,
+    pub beyond_end: bool,
+}
+
+/// Why a password was accepted or rejected.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    /// The character has to occur a number of times within `allowed`.
+    Count {
+        character: char,
+        count: usize,
+        allowed: RangeInclusive<usize>,
+    },
+
+    /// The character has to be at exactly one of the positions.
+    Positions {
+        character: char,
+        length: usize,
+        first: Position,
+        second: Position,
+    },
+
+    /// Any other kind of policy.
+    Rule { valid: bool, reason: String },
+}
+
+impl Verdict {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Verdict::Count { count, allowed, .. } => allowed.contains(count),
+            Verdict::Positions { first, second, .. } => first.matches != second.matches,
+            Verdict::Rule { valid, .. } => *valid,
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Count {
+                character,
+                count,
+                allowed,
+            } => write!(
+                f,
+                "{:?} occurs {} times, allowed {}-{}",
+                character,
+                count,
+                allowed.start(),
+                allowed.end()
+            ),
+            Verdict::Positions {
+                character,
+                length,
+                first,
+                second,
+            } => {
+                match (first.matches, second.matches) {
+                    (true, true) => write!(
+                        f,
+                        "{:?} at both positions {} and {}",
+                        character, first.at, second.at
+                    )?,
+                    (false, false) => write!(
+                        f,
+                        "{:?} at neither position {} nor {}",
+                        character, first.at, second.at
+                    )?,
+                    (true, false) => write!(f, "{:?} only at position {}", character, first.at)?,
+                    (false, true) => write!(f, "{:?} only at position {}", character, second.at)?,
+                }
+
+                for position in [first, second].iter().filter(|p| p.beyond_end) {
+                    write!(
+                        f,
+                        ", position {} is beyond the password length {}",
+                        position.at, length
+                    )?;
+                }
+
+                Ok(())
+            }
+            Verdict::Rule { reason, .. } => write!(f, "{}", reason),
+        }
+    }
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("unknown policy kind {0:?}")]
+    UnknownKind(String),
+
+    #[error("invalid {kind} policy: {message}")]
+    InvalidPolicy { kind: String, message: String },
+}
+
+type Parser = fn(&str) -> Result<Box<dyn PasswordPolicy>, String>;
+
+fn parse<P>(s: &str) -> Result<Box<dyn PasswordPolicy>, String>
+where
+    P: PasswordPolicy + FromStr + 'static,
+    P::Err: Display,
+{
+    s.parse::<P>()
+        .map(|policy| Box::new(policy) as Box<dyn PasswordPolicy>)
+        .map_err(|e| e.to_string())
+}
+
+/// Policy parsers by kind name. The default knows every built-in kind.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    parsers: BTreeMap<String, Parser>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::empty()
+            .with::<sled_rental::PasswordPolicy>("sled")
+            .with::<toboggan_rental::PasswordPolicy>("toboggan")
+            .with::<regex::PasswordPolicy>("regex")
+            .with::<min_length::PasswordPolicy>("min_length")
+            .with::<character_classes::PasswordPolicy>("character_classes")
+            .with::<forbidden_substrings::PasswordPolicy>("forbidden_substrings")
+    }
+}
+
+impl Registry {
+    pub fn empty() -> Self {
+        Self {
+            parsers: BTreeMap::new(),
+        }
+    }
+
+    pub fn with<P>(mut self, kind: &str) -> Self
+    where
+        P: PasswordPolicy + FromStr + 'static,
+        P::Err: Display,
+    {
+        self.parsers.insert(kind.to_string(), parse::<P>);
+        self
+    }
+
+    pub fn kinds(&self) -> impl Iterator<Item = &str> {
+        self.parsers.keys().map(String::as_str)
+    }
+
+    pub fn parse(&self, kind: &str, policy: &str) -> Result<Box<dyn PasswordPolicy>, Error> {
+        let parser = self
+            .parsers
+            .get(kind)
+            .ok_or_else(|| Error::UnknownKind(kind.to_string()))?;
+
+        parser(policy).map_err(|message| Error::InvalidPolicy {
+            kind: kind.to_string(),
+            message,
+        })
+    }
+}
+
 pub(super) mod sled_rental {
+    use super::Verdict;
     use scan_fmt::scan_fmt;
     use thiserror::Error;
 
@@ -15,19 +204,15 @@ pub(super) mod sled_rental {
         character: char,
     }
 
-    impl PasswordPolicy {
-        pub fn is_valid_password(&self, password: &str) -> bool {
+    impl super::PasswordPolicy for PasswordPolicy {
+        fn check(&self, password: &str) -> Verdict {
             let occurences = password.chars().filter(|ch| *ch == self.character).count();
 
-            if occurences < self.min {
-                return false;
+            Verdict::Count {
+                character: self.character,
+                count: occurences,
+                allowed: self.min..=self.max,
             }
-
-            if occurences > self.max {
-                return false;
-            }
-
-            true
         }
     }
 
@@ -64,12 +249,81 @@ pub(super) mod sled_rental {
 
             assert_eq!(expected, got);
         }
+
+        #[test]
+        fn check() {
+            use super::super::PasswordPolicy;
+
+            let policy = super::PasswordPolicy::from_str("1-3 b").expect("bad input");
+            let verdict = policy.check("cdefg");
+
+            assert!(!verdict.is_valid());
+            assert_eq!("'b' occurs 0 times, allowed 1-3", verdict.to_string());
+        }
     }
 }
 
 pub(super) mod toboggan_rental {
+    use super::{
+        Position,
+        Verdict,
+    };
     use scan_fmt::scan_fmt;
     use thiserror::Error;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    /// How positions in a password are counted, given after the character,
+    /// e.g. `1-3 a bytes`. Defaults to chars.
+    #[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+    pub enum Unit {
+        /// A position matches if the character starts at that byte.
+        Bytes,
+
+        #[default]
+        Chars,
+
+        /// Extended grapheme clusters, a position matches if the cluster is
+        /// exactly the character. Combining sequences are not normalized.
+        Graphemes,
+    }
+
+    impl Unit {
+        fn len(self, password: &str) -> usize {
+            match self {
+                Unit::Bytes => password.len(),
+                Unit::Chars => password.chars().count(),
+                Unit::Graphemes => password.graphemes(true).count(),
+            }
+        }
+
+        fn matches(self, password: &str, index: usize, character: char) -> bool {
+            match self {
+                Unit::Bytes => password
+                    .get(index..)
+                    .is_some_and(|rest| rest.starts_with(character)),
+                Unit::Chars => password.chars().nth(index) == Some(character),
+                Unit::Graphemes => {
+                    let mut buffer = [0; 4];
+                    let character = character.encode_utf8(&mut buffer);
+
+                    password.graphemes(true).nth(index) == Some(character)
+                }
+            }
+        }
+    }
+
+    impl std::str::FromStr for Unit {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "bytes" => Ok(Self::Bytes),
+                "chars" => Ok(Self::Chars),
+                "graphemes" => Ok(Self::Graphemes),
+                _ => Err(Error::InvalidPolicy(format!("unknown unit {:?}", s))),
+            }
+        }
+    }
 
     #[derive(Debug, Error, Eq, PartialEq)]
     pub enum Error {
@@ -82,27 +336,25 @@ pub(super) mod toboggan_rental {
         contains_index: usize,
         not_contains_index: usize,
         character: char,
+        unit: Unit,
     }
 
-    impl PasswordPolicy {
-        pub fn is_valid_password(&self, password: &str) -> bool {
-            let chars = password.chars().collect::<Vec<_>>();
+    impl super::PasswordPolicy for PasswordPolicy {
+        fn check(&self, password: &str) -> Verdict {
+            let length = self.unit.len(password);
 
-            let contains_char = chars[self.contains_index];
-            let not_contains_char = chars[self.not_contains_index];
-
-            let contains = contains_char == self.character;
-            let not_contains = not_contains_char != self.character;
-
-            if contains && not_contains {
-                return true;
-            }
+            let position = |index: usize| Position {
+                at: index + 1,
+                matches: self.unit.matches(password, index, self.character),
+                beyond_end: index >= length,
+            };
 
-            if !contains && !not_contains {
-                return true;
+            Verdict::Positions {
+                character: self.character,
+                length,
+                first: position(self.contains_index),
+                second: position(self.not_contains_index),
             }
-
-            false
         }
     }
 
@@ -110,18 +362,31 @@ pub(super) mod toboggan_rental {
         type Err = Error;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
+            // The unit is an optional third word after positions and character
+            let (s, unit) = match s.trim().rsplit_once(char::is_whitespace) {
+                Some((policy, unit)) if policy.trim().contains(char::is_whitespace) => {
+                    (policy, unit.parse()?)
+                }
+                _ => (s, Unit::default()),
+            };
+
             let (contains_index, not_contains_index, character) =
                 scan_fmt!(s, "{}-{} {}", usize, usize, char)
                     .map_err(|e| Error::InvalidPolicy(format!("{}", e)))?;
 
             // Make values one smaller so we have a zero indexed value for the index
-            let contains_index = contains_index - 1;
-            let not_contains_index = not_contains_index - 1;
+            let (Some(contains_index), Some(not_contains_index)) = (
+                contains_index.checked_sub(1),
+                not_contains_index.checked_sub(1),
+            ) else {
+                return Err(Error::InvalidPolicy("positions start at 1".into()));
+            };
 
             Ok(Self {
                 contains_index,
                 not_contains_index,
                 character,
+                unit,
             })
         }
     }
@@ -138,11 +403,437 @@ pub(super) mod toboggan_rental {
                 contains_index: 0,
                 not_contains_index: 2,
                 character: 'a',
+                unit: super::Unit::Chars,
             });
 
             let got = super::PasswordPolicy::from_str(INPUT);
 
             assert_eq!(expected, got);
         }
+
+        #[test]
+        fn check() {
+            use super::super::PasswordPolicy;
+
+            let policy = super::PasswordPolicy::from_str("2-9 c").expect("bad input");
+
+            let cases = vec![
+                ("cdccccccc", true, "'c' only at position 9"),
+                ("ccccccccc", false, "'c' at both positions 2 and 9"),
+                (
+                    "abcde",
+                    false,
+                    "'c' at neither position 2 nor 9, position 9 is beyond the password length 5",
+                ),
+                (
+                    "acb",
+                    true,
+                    "'c' only at position 2, position 9 is beyond the password length 3",
+                ),
+            ];
+
+            for (password, valid, reason) in cases {
+                let verdict = policy.check(password);
+
+                assert_eq!(valid, verdict.is_valid(), "{}", password);
+                assert_eq!(reason, verdict.to_string());
+            }
+        }
+
+        #[test]
+        fn from_str_unit() {
+            let got = super::PasswordPolicy::from_str("1-3 ä graphemes");
+
+            assert_eq!(
+                Ok(super::PasswordPolicy {
+                    contains_index: 0,
+                    not_contains_index: 2,
+                    character: 'ä',
+                    unit: super::Unit::Graphemes,
+                }),
+                got
+            );
+            assert_eq!(
+                Err(super::Error::InvalidPolicy("unknown unit \"words\"".into())),
+                super::PasswordPolicy::from_str("1-3 a words")
+            );
+        }
+
+        #[test]
+        fn non_ascii() {
+            use super::super::PasswordPolicy;
+
+            let cases = vec![
+                // Both 'ä' are at char positions 1 and 2
+                ("1-2 ä", "ääb", false),
+                // but at bytes 1 and 3, byte 2 is inside the first 'ä'
+                ("1-3 ä bytes", "ääb", false),
+                ("2-3 ä bytes", "ääb", true),
+                // 'e' followed by a combining acute accent is one grapheme
+                ("1-2 b", "e\u{301}b", false),
+                ("1-2 b graphemes", "e\u{301}b", true),
+                ("1-2 e graphemes", "e\u{301}b", false),
+                ("1-2 é graphemes", "e\u{301}b", false),
+                ("1-2 é graphemes", "\u{e9}b", true),
+                // A family emoji is five chars but a single grapheme
+                (
+                    "2-6 x graphemes",
+                    "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}x",
+                    true,
+                ),
+                (
+                    "2-6 x",
+                    "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}x",
+                    true,
+                ),
+                (
+                    "2-3 x",
+                    "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}x",
+                    false,
+                ),
+            ];
+
+            for (policy, password, expected) in cases {
+                let policy = super::PasswordPolicy::from_str(policy).expect("bad input");
+
+                assert_eq!(expected, policy.is_valid_password(password), "{:?}", policy);
+            }
+        }
+
+        #[test]
+        fn length_in_unit() {
+            use super::super::PasswordPolicy;
+
+            let policy = super::PasswordPolicy::from_str("1-4 b graphemes").expect("bad input");
+
+            assert_eq!(
+                "'b' at neither position 1 nor 4, position 4 is beyond the password length 2",
+                policy.check("e\u{301}b").to_string()
+            );
+        }
+
+        #[test]
+        fn from_str_zero_position() {
+            assert_eq!(
+                Err(super::Error::InvalidPolicy("positions start at 1".into())),
+                super::PasswordPolicy::from_str("0-3 a")
+            );
+        }
+    }
+}
+
+/// Passwords matching a regular expression, the policy is the pattern.
+pub(super) mod regex {
+    use super::Verdict;
+    use regex::Regex;
+
+    #[derive(Debug)]
+    pub struct PasswordPolicy {
+        pattern: Regex,
+    }
+
+    impl super::PasswordPolicy for PasswordPolicy {
+        fn check(&self, password: &str) -> Verdict {
+            let valid = self.pattern.is_match(password);
+            let reason = if valid {
+                format!("matches {}", self.pattern)
+            } else {
+                format!("does not match {}", self.pattern)
+            };
+
+            Verdict::Rule { valid, reason }
+        }
+    }
+
+    impl std::str::FromStr for PasswordPolicy {
+        type Err = regex::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self {
+                pattern: Regex::new(s)?,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::super::PasswordPolicy;
+
+        #[test]
+        fn is_valid_password() {
+            let policy = "^[a-z]+[0-9]$"
+                .parse::<super::PasswordPolicy>()
+                .expect("bad input");
+
+            assert!(policy.is_valid_password("abc1"));
+            assert!(!policy.is_valid_password("abc"));
+        }
+    }
+}
+
+/// Passwords with at least the given number of characters.
+pub(super) mod min_length {
+    use super::Verdict;
+
+    #[derive(Debug, Eq, PartialEq)]
+    pub struct PasswordPolicy {
+        min: usize,
+    }
+
+    impl super::PasswordPolicy for PasswordPolicy {
+        fn check(&self, password: &str) -> Verdict {
+            let length = password.chars().count();
+
+            Verdict::Rule {
+                valid: length >= self.min,
+                reason: format!("length {}, expected at least {}", length, self.min),
+            }
+        }
+    }
+
+    impl std::str::FromStr for PasswordPolicy {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self {
+                min: s.trim().parse()?,
+            })
+        }
+    }
+}
+
+/// Passwords containing at least one character of every listed class, e.g.
+/// `lower upper digit`.
+pub(super) mod character_classes {
+    use super::Verdict;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, Eq, PartialEq)]
+    pub enum Error {
+        #[error("unknown character class {0:?}")]
+        UnknownClass(String),
+
+        #[error("no character classes given")]
+        Empty,
+    }
+
+    #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+    pub enum Class {
+        Lower,
+        Upper,
+        Digit,
+        Symbol,
+    }
+
+    impl Class {
+        fn contains(self, ch: char) -> bool {
+            match self {
+                Class::Lower => ch.is_lowercase(),
+                Class::Upper => ch.is_uppercase(),
+                Class::Digit => ch.is_numeric(),
+                Class::Symbol => !ch.is_alphanumeric() && !ch.is_whitespace(),
+            }
+        }
+    }
+
+    impl std::fmt::Display for Class {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let name = match self {
+                Class::Lower => "lower",
+                Class::Upper => "upper",
+                Class::Digit => "digit",
+                Class::Symbol => "symbol",
+            };
+
+            write!(f, "{}", name)
+        }
+    }
+
+    impl std::str::FromStr for Class {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "lower" => Ok(Self::Lower),
+                "upper" => Ok(Self::Upper),
+                "digit" => Ok(Self::Digit),
+                "symbol" => Ok(Self::Symbol),
+                _ => Err(Error::UnknownClass(s.into())),
+            }
+        }
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    pub struct PasswordPolicy {
+        classes: Vec<Class>,
+    }
+
+    impl super::PasswordPolicy for PasswordPolicy {
+        fn check(&self, password: &str) -> Verdict {
+            let missing = self
+                .classes
+                .iter()
+                .filter(|class| !password.chars().any(|ch| class.contains(ch)))
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            Verdict::Rule {
+                valid: missing.is_empty(),
+                reason: if missing.is_empty() {
+                    "contains all character classes".to_string()
+                } else {
+                    format!("missing {}", missing.join(", "))
+                },
+            }
+        }
+    }
+
+    impl std::str::FromStr for PasswordPolicy {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let classes = s
+                .split(|ch: char| ch == ',' || ch.is_whitespace())
+                .filter(|class| !class.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if classes.is_empty() {
+                return Err(Error::Empty);
+            }
+
+            Ok(Self { classes })
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::{
+            super::PasswordPolicy,
+            Error,
+        };
+
+        #[test]
+        fn is_valid_password() {
+            let policy = "lower, upper digit"
+                .parse::<super::PasswordPolicy>()
+                .expect("bad input");
+
+            assert!(policy.is_valid_password("aB3"));
+            assert!(!policy.is_valid_password("ab3"));
+            assert_eq!("missing upper, digit", policy.check("ab").to_string());
+        }
+
+        #[test]
+        fn from_str() {
+            assert_eq!(
+                Err(Error::UnknownClass("emoji".into())),
+                "lower emoji".parse::<super::PasswordPolicy>()
+            );
+            assert_eq!(Err(Error::Empty), " ".parse::<super::PasswordPolicy>());
+        }
+    }
+}
+
+/// Passwords not containing any of the comma separated substrings.
+pub(super) mod forbidden_substrings {
+    use super::Verdict;
+
+    #[derive(Debug, Eq, PartialEq)]
+    pub struct PasswordPolicy {
+        substrings: Vec<String>,
+    }
+
+    impl super::PasswordPolicy for PasswordPolicy {
+        fn check(&self, password: &str) -> Verdict {
+            let found = self
+                .substrings
+                .iter()
+                .find(|substring| password.contains(substring.as_str()));
+
+            match found {
+                Some(substring) => Verdict::Rule {
+                    valid: false,
+                    reason: format!("contains {:?}", substring),
+                },
+                None => Verdict::Rule {
+                    valid: true,
+                    reason: "contains no forbidden substring".to_string(),
+                },
+            }
+        }
+    }
+
+    impl std::str::FromStr for PasswordPolicy {
+        type Err = std::convert::Infallible;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let substrings = s
+                .split(',')
+                .map(str::trim)
+                .filter(|substring| !substring.is_empty())
+                .map(ToString::to_string)
+                .collect();
+
+            Ok(Self { substrings })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Error,
+        Registry,
+    };
+
+    mod registry {
+        use super::{
+            Error,
+            Registry,
+        };
+
+        #[test]
+        fn parse() {
+            let registry = Registry::default();
+
+            let cases = vec![
+                ("sled", "1-3 a", "abcde", true),
+                ("toboggan", "2-9 c", "ccccccccc", false),
+                ("min_length", "8", "hunter2", false),
+                ("forbidden_substrings", "123, password", "mypassword", false),
+                ("forbidden_substrings", "123, password", "secret", true),
+            ];
+
+            for (kind, policy, password, expected) in cases {
+                let policy = registry.parse(kind, policy).expect("bad input");
+
+                assert_eq!(expected, policy.is_valid_password(password));
+            }
+        }
+
+        #[test]
+        fn errors() {
+            let registry = Registry::default();
+
+            assert_eq!(
+                Some(Error::UnknownKind("bogus".into())),
+                registry.parse("bogus", "1-3 a").err()
+            );
+            assert!(matches!(
+                registry.parse("regex", "[a-"),
+                Err(Error::InvalidPolicy { .. })
+            ));
+        }
+
+        #[test]
+        fn custom_kind() {
+            let registry =
+                Registry::empty().with::<super::super::min_length::PasswordPolicy>("len");
+
+            assert_eq!(vec!["len"], registry.kinds().collect::<Vec<_>>());
+            assert!(registry.parse("len", "3").is_ok());
+            assert!(registry.parse("sled", "1-3 a").is_err());
+        }
     }
 }
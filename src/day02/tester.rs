@@ -1,68 +1,159 @@
 use super::{
+    password_policy::{
+        PasswordPolicy,
+        Registry,
+        Verdict,
+    },
     Error,
     PasswordPolicySledRental,
     PasswordPolicyTobogganRental,
 };
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    str::FromStr,
+};
+
+pub(super) type SledTester<'a> = Tester<'a, PasswordPolicySledRental>;
+pub(super) type TobogganTester<'a> = Tester<'a, PasswordPolicyTobogganRental>;
+
+type Parser<'a, P> = Box<dyn Fn(&str) -> Result<P, String> + 'a>;
+
+/// Splits an entry into policy and password at the first `:`. Both are
+/// trimmed, the password may contain further `:`.
+pub(super) fn split(entry: &str) -> Result<(&str, &str), Error> {
+    let (policy, password) = entry.split_once(':').ok_or_else(|| Error::InvalidEntry {
+        entry: entry.to_string(),
+        reason: "expected \"policy: password\"".into(),
+    })?;
 
-pub(super) struct SledTester<'a> {
-    policies: HashMap<&'a str, PasswordPolicySledRental>,
+    let policy = policy.trim();
+    if policy.is_empty() {
+        return Err(Error::InvalidEntry {
+            entry: entry.to_string(),
+            reason: "missing policy".into(),
+        });
+    }
+
+    Ok((policy, password.trim()))
 }
 
-impl<'a> SledTester<'a> {
+/// Tests "policy: password" entries, parsing every distinct policy once.
+pub(super) struct Tester<'a, P> {
+    parse: Parser<'a, P>,
+    policies: HashMap<&'a str, P>,
+}
+
+impl<P> Tester<'_, P>
+where
+    P: PasswordPolicy + FromStr,
+    P::Err: Display,
+{
     pub fn new() -> Self {
+        Self::with_parser(Box::new(|policy| {
+            policy.parse().map_err(|e: P::Err| e.to_string())
+        }))
+    }
+}
+
+impl<'a> Tester<'a, Box<dyn PasswordPolicy>> {
+    /// Tester for policies of `kind` as known to the registry.
+    #[allow(dead_code)]
+    pub fn with_registry(registry: &'a Registry, kind: &'a str) -> Self {
+        Self::with_parser(Box::new(move |policy| {
+            registry.parse(kind, policy).map_err(|e| e.to_string())
+        }))
+    }
+}
+
+impl<'a, P: PasswordPolicy> Tester<'a, P> {
+    fn with_parser(parse: Parser<'a, P>) -> Self {
         Self {
+            parse,
             policies: HashMap::new(),
         }
     }
 
-    pub fn test(&mut self, input: &'a str) -> Result<bool, Error> {
-        let mut policy_password_split = input.split(':');
-        let policy_raw = policy_password_split.next().ok_or(Error::MissingPolicy)?;
+    pub fn test(&mut self, entry: &'a str) -> Result<bool, Error> {
+        Ok(self.check(entry)?.is_valid())
+    }
+
+    /// Checks an entry and explains the verdict.
+    pub fn check(&mut self, entry: &'a str) -> Result<Verdict, Error> {
+        let (policy_raw, password) = split(entry)?;
 
-        let policy = self
-            .policies
-            .entry(policy_raw)
-            .or_insert_with(|| policy_raw.parse().expect("bad input"));
+        if !self.policies.contains_key(policy_raw) {
+            let policy = (self.parse)(policy_raw).map_err(|reason| Error::InvalidEntry {
+                entry: entry.to_string(),
+                reason,
+            })?;
 
-        let password = policy_password_split
-            .next()
-            .ok_or(Error::MissingPassword)?
-            .trim();
+            self.policies.insert(policy_raw, policy);
+        }
 
-        let valid = policy.is_valid_password(password);
+        let policy = &self.policies[policy_raw];
 
-        Ok(valid)
+        Ok(policy.check(password))
     }
 }
 
-pub(super) struct TobogganTester<'a> {
-    policies: HashMap<&'a str, PasswordPolicyTobogganRental>,
-}
+#[cfg(test)]
+mod test {
+    use super::{
+        super::password_policy::Registry,
+        Error,
+        SledTester,
+        Tester,
+        TobogganTester,
+    };
 
-impl<'a> TobogganTester<'a> {
-    pub fn new() -> Self {
-        Self {
-            policies: HashMap::new(),
-        }
-    }
+    #[test]
+    fn registry() {
+        let registry = Registry::default();
+        let mut tester = Tester::with_registry(&registry, "min_length");
 
-    pub fn test(&mut self, input: &'a str) -> Result<bool, Error> {
-        let mut policy_password_split = input.split(':');
-        let policy_raw = policy_password_split.next().ok_or(Error::MissingPolicy)?;
+        assert_eq!(Ok(true), tester.test("4: hunter2"));
+        assert_eq!(Ok(false), tester.test("8: hunter2"));
+    }
 
-        let policy = self
-            .policies
-            .entry(policy_raw)
-            .or_insert_with(|| policy_raw.parse().expect("bad input"));
+    #[test]
+    fn separators() {
+        let mut tester = SledTester::new();
 
-        let password = policy_password_split
-            .next()
-            .ok_or(Error::MissingPassword)?
-            .trim();
+        assert_eq!(Ok(true), tester.test("1-3 a: a:b:c"));
+        assert_eq!(Ok(true), tester.test("  1-3 a  :   abcde  "));
+        assert_eq!(Ok(false), tester.test("1-3 a: b:c::"));
+    }
 
-        let valid = policy.is_valid_password(password);
+    #[test]
+    fn errors() {
+        let mut tester = TobogganTester::new();
 
-        Ok(valid)
+        assert_eq!(
+            Err(Error::InvalidEntry {
+                entry: "1-3 a abcde".into(),
+                reason: "expected \"policy: password\"".into(),
+            }),
+            tester.test("1-3 a abcde")
+        );
+        assert_eq!(
+            Err(Error::InvalidEntry {
+                entry: " : abcde".into(),
+                reason: "missing policy".into(),
+            }),
+            tester.test(" : abcde")
+        );
+        assert_eq!(
+            Err(Error::InvalidEntry {
+                entry: "0-3 a: abcde".into(),
+                reason: "invalid input for policy: positions start at 1".into(),
+            }),
+            tester.test("0-3 a: abcde")
+        );
+        assert!(matches!(
+            tester.test("a-b c: abcde"),
+            Err(Error::InvalidEntry { .. })
+        ));
+        assert_eq!(Ok(true), tester.test("1-9 a: abc"));
     }
 }
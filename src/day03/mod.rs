@@ -6,6 +6,9 @@ mod rider;
 pub enum Error {
     #[error("can not read map: {0}")]
     MapError(#[from] rider::map::Error),
+
+    #[error("can not ride map: {0}")]
+    RideError(#[from] rider::Error),
 }
 
 pub fn run() -> Result<(), Error> {
@@ -20,7 +23,7 @@ pub fn part_1() -> Result<usize, Error> {
     let (right, down) = (3, 1);
 
     let mut rider = rider::new(input);
-    rider.ride(right, down);
+    rider.ride((right, down).into())?;
 
     Ok(rider.trees_seen)
 }
@@ -34,13 +37,13 @@ pub fn part_2() -> Result<usize, Error> {
     let trees_seen = slopes
         .into_iter()
         .map(|(right, down)| {
-            rider.ride(right, down);
+            rider.ride((right, down).into())?;
             let trees_seen = rider.trees_seen;
             rider.reset();
 
-            trees_seen
+            Ok(trees_seen)
         })
-        .product::<usize>();
+        .product::<Result<usize, Error>>()?;
 
     Ok(trees_seen)
 }
@@ -1,11 +1,18 @@
+use std::ops::{
+    Add,
+    AddAssign,
+    Mul,
+};
+
+/// Position on the map. `x` grows to the right and `y` grows downwards.
 #[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default)]
 pub struct Coordinate {
-    pub x: usize,
-    pub y: usize,
+    pub x: isize,
+    pub y: isize,
 }
 
-impl From<(usize, usize)> for Coordinate {
-    fn from(tuple: (usize, usize)) -> Self {
+impl From<(isize, isize)> for Coordinate {
+    fn from(tuple: (isize, isize)) -> Self {
         Self {
             x: tuple.0,
             y: tuple.1,
@@ -14,17 +21,148 @@ impl From<(usize, usize)> for Coordinate {
 }
 
 impl Coordinate {
-    pub fn step_right(self, steps: usize) -> Self {
+    pub fn step_right(self, steps: isize) -> Self {
         Self {
             x: self.x + steps,
             y: self.y,
         }
     }
 
-    pub fn step_down(self, steps: usize) -> Self {
+    pub fn step_down(self, steps: isize) -> Self {
         Self {
             x: self.x,
             y: self.y + steps,
         }
     }
 }
+
+impl Add<Vector> for Coordinate {
+    type Output = Self;
+
+    fn add(self, vector: Vector) -> Self::Output {
+        self.step_right(vector.x).step_down(vector.y)
+    }
+}
+
+impl AddAssign<Vector> for Coordinate {
+    fn add_assign(&mut self, vector: Vector) {
+        *self = *self + vector;
+    }
+}
+
+/// Direction and distance of a single move. Uses the same axes as
+/// [`Coordinate`].
+#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Default)]
+pub struct Vector {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl From<(isize, isize)> for Vector {
+    fn from(tuple: (isize, isize)) -> Self {
+        Self {
+            x: tuple.0,
+            y: tuple.1,
+        }
+    }
+}
+
+impl Vector {
+    pub fn is_zero(self) -> bool {
+        self.x == 0 && self.y == 0
+    }
+
+    pub fn scale(self, factor: isize) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+
+    /// Rotates by 90 degrees clockwise as seen on the map, so right turns into
+    /// down.
+    #[allow(dead_code)]
+    pub fn rotate_right(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Rotates by 90 degrees counterclockwise as seen on the map, so right
+    /// turns into up.
+    #[allow(dead_code)]
+    pub fn rotate_left(self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Self;
+
+    fn mul(self, factor: isize) -> Self::Output {
+        self.scale(factor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Coordinate,
+        Vector,
+    };
+
+    #[test]
+    fn add() {
+        let expected = Coordinate { x: 2, y: 4 };
+        let got = Coordinate { x: 3, y: 1 } + Vector { x: -1, y: 3 };
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn scale() {
+        let expected = Vector { x: -6, y: 3 };
+        let got = Vector { x: -2, y: 1 } * 3;
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn rotate_right() {
+        let right = Vector { x: 1, y: 0 };
+        let down = Vector { x: 0, y: 1 };
+
+        assert_eq!(down, right.rotate_right());
+        assert_eq!(
+            right,
+            right
+                .rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right()
+        );
+    }
+
+    #[test]
+    fn rotate_left() {
+        let slope = Vector { x: 3, y: 1 };
+
+        assert_eq!(Vector { x: 1, y: -3 }, slope.rotate_left());
+        assert_eq!(slope, slope.rotate_left().rotate_right());
+    }
+}
@@ -1,95 +1,152 @@
-use std::convert::TryInto;
+use std::convert::{
+    TryFrom,
+    TryInto,
+};
 use thiserror::Error;
 
 use super::{
     coordinate::Coordinate,
-    tile::Tile,
+    tile::{
+        Tile,
+        Tileset,
+    },
+};
+use crate::grid::{
+    self,
+    Edge,
+    Grid,
 };
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Eq, PartialEq)]
 pub enum Error {
-    #[error("invalid tile found: {0}")]
-    InvalidTile(super::tile::Error),
+    #[error("invalid tile found in line {line} column {column}: {source}")]
+    InvalidTile {
+        line: usize,
+        column: usize,
+        source: super::tile::Error,
+    },
+
+    #[error("line {line} has {got} tiles but the map is {expected} tiles wide")]
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl From<grid::Error> for Error {
+    fn from(err: grid::Error) -> Self {
+        match err {
+            grid::Error::RaggedRow { row, expected, got } => Self::RaggedLine {
+                line: row + 1,
+                expected,
+                got,
+            },
+
+            grid::Error::InvalidSize { .. } => unreachable!("grid is built from rows"),
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Map {
-    entries: Vec<Vec<Tile>>,
-    max_coordinate: Coordinate,
+    entries: Grid<Tile>,
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        Vec::new().try_into().expect("empty map is valid")
+    }
 }
 
 impl std::str::FromStr for Map {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let entries = s
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|ch| ch.try_into().map_err(Error::InvalidTile))
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(entries.into())
+        Self::parse(s, TryInto::try_into)
     }
 }
 
-impl From<Vec<Vec<Tile>>> for Map {
-    fn from(entries: Vec<Vec<Tile>>) -> Self {
-        let max_y = entries.len() - 1;
-        let max_x = entries.get(0).map_or(0, |line| line.len() - 1);
-        let max_coordinate = Coordinate { x: max_x, y: max_y };
+impl TryFrom<Vec<Vec<Tile>>> for Map {
+    type Error = Error;
 
-        Self {
-            entries,
-            max_coordinate,
-        }
+    fn try_from(entries: Vec<Vec<Tile>>) -> Result<Self, Self::Error> {
+        let entries = Grid::from_rows(entries, Edge::WrapHorizontal)?;
+
+        Ok(Self { entries })
     }
 }
 
 impl Map {
-    pub fn get_tile(&self, coordinate: &Coordinate) -> Option<&Tile> {
-        let max_y = self.max_y();
-        if coordinate.y > max_y {
-            return None;
-        }
+    /// Parses the map with a custom character to tile mapping.
+    #[allow(dead_code)]
+    pub fn parse_with(s: &str, tileset: &Tileset) -> Result<Self, Error> {
+        Self::parse(s, |ch| tileset.tile(ch))
+    }
 
-        let max_x = self.max_x();
+    /// Line and column in errors are counted from 1.
+    fn parse<F>(s: &str, to_tile: F) -> Result<Self, Error>
+    where
+        F: Fn(char) -> Result<Tile, super::tile::Error>,
+    {
+        let entries = s
+            .lines()
+            .enumerate()
+            .map(|(line, entries)| {
+                entries
+                    .chars()
+                    .enumerate()
+                    .map(|(column, ch)| {
+                        to_tile(ch).map_err(|source| Error::InvalidTile {
+                            line: line + 1,
+                            column: column + 1,
+                            source,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let check_coordinate = if coordinate.x > max_x {
-            (coordinate.x % (max_x + 1), coordinate.y).into()
-        } else {
-            *coordinate
-        };
+        entries.try_into()
+    }
 
-        Some(&self.entries[check_coordinate.y][check_coordinate.x])
+    pub fn get_tile(&self, coordinate: &Coordinate) -> Option<&Tile> {
+        self.entries.get((coordinate.x, coordinate.y))
     }
 
-    pub fn max_y(&self) -> usize {
-        self.max_coordinate.y
+    pub fn width(&self) -> usize {
+        self.entries.width()
     }
 
-    pub fn max_x(&self) -> usize {
-        self.max_coordinate.x
+    pub fn height(&self) -> usize {
+        self.entries.height()
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::{
+        Error,
         Map,
         Tile,
+        Tileset,
     };
 
     mod from_str {
-        use super::Tile;
-        use std::str::FromStr;
+        use super::{
+            Error,
+            Tile,
+        };
+        use crate::day03::rider::tile::Error as TileError;
+        use std::{
+            convert::TryFrom,
+            str::FromStr,
+        };
 
         #[test]
         fn minimal() {
             const INPUT: &str = ".";
-            let expected: super::Map = vec![vec![Tile::Air]].into();
+            let expected = super::Map::try_from(vec![vec![Tile::Air]]).expect("invalid map");
             let got = super::Map::from_str(INPUT).expect("invalid input");
 
             assert_eq!(expected, got);
@@ -98,7 +155,8 @@ mod test {
         #[test]
         fn multiline() {
             const INPUT: &str = ".\n#";
-            let expected: super::Map = vec![vec![Tile::Air], vec![Tile::Tree]].into();
+            let expected =
+                super::Map::try_from(vec![vec![Tile::Air], vec![Tile::Tree]]).expect("invalid map");
             let got = super::Map::from_str(INPUT).expect("invalid input");
 
             assert_eq!(expected, got);
@@ -108,20 +166,87 @@ mod test {
         fn multi_dimension() {
             const INPUT: &str = ".#\n#.";
 
-            let expected: super::Map =
-                vec![vec![Tile::Air, Tile::Tree], vec![Tile::Tree, Tile::Air]].into();
+            let expected = super::Map::try_from(vec![
+                vec![Tile::Air, Tile::Tree],
+                vec![Tile::Tree, Tile::Air],
+            ])
+            .expect("invalid map");
             let got = super::Map::from_str(INPUT).expect("invalid input");
 
             assert_eq!(expected, got);
         }
+
+        #[test]
+        fn empty() {
+            let got = super::Map::from_str("").expect("invalid input");
+
+            assert_eq!(super::Map::default(), got);
+            assert_eq!(None, got.get_tile(&(0, 0).into()));
+        }
+
+        #[test]
+        fn invalid_tile() {
+            const INPUT: &str = "..\n.x";
+
+            let expected = Err(Error::InvalidTile {
+                line: 2,
+                column: 2,
+                source: TileError::UnkownTile('x'),
+            });
+            let got = super::Map::from_str(INPUT);
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn ragged_line() {
+            const INPUT: &str = "..\n.\n..";
+
+            let expected = Err(Error::RaggedLine {
+                line: 2,
+                expected: 2,
+                got: 1,
+            });
+            let got = super::Map::from_str(INPUT);
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    mod parse_with {
+        use super::{
+            Map,
+            Tile,
+            Tileset,
+        };
+        use std::convert::TryFrom;
+
+        #[test]
+        fn extra_tiles() {
+            const INPUT: &str = ".^\n*#";
+
+            let tileset = Tileset::default()
+                .with('^', Tile::Rock)
+                .with('*', Tile::Snow);
+
+            let expected = Map::try_from(vec![
+                vec![Tile::Air, Tile::Rock],
+                vec![Tile::Snow, Tile::Tree],
+            ])
+            .expect("invalid map");
+            let got = Map::parse_with(INPUT, &tileset).expect("invalid input");
+
+            assert_eq!(expected, got);
+        }
     }
 
     mod get_tile {
         use super::Tile;
+        use std::convert::TryFrom;
 
         #[test]
         fn single_column_no_trackback() {
-            let map: super::Map = vec![vec![Tile::Air]].into();
+            let map = super::Map::try_from(vec![vec![Tile::Air]]).expect("invalid map");
             let expected = Some(&Tile::Air);
             let got = map.get_tile(&(0, 0).into());
 
@@ -130,7 +255,7 @@ mod test {
 
         #[test]
         fn single_column_trackback() {
-            let map: super::Map = vec![vec![Tile::Air]].into();
+            let map = super::Map::try_from(vec![vec![Tile::Air]]).expect("invalid map");
             let expected = Some(&Tile::Air);
             let got = map.get_tile(&(1, 0).into());
 
@@ -199,12 +324,15 @@ mod bench {
 
     mod get_tile {
         use super::Tile;
-        use std::str::FromStr;
+        use std::{
+            convert::TryFrom,
+            str::FromStr,
+        };
         use test::Bencher;
 
         #[bench]
         fn single_column_no_trackback(b: &mut Bencher) {
-            let map: super::Map = vec![vec![Tile::Air]].into();
+            let map = super::Map::try_from(vec![vec![Tile::Air]]).expect("invalid map");
             let coordinate: super::Coordinate = test::black_box((0, 0).into());
 
             b.iter(|| {
@@ -214,7 +342,7 @@ mod bench {
 
         #[bench]
         fn single_column_trackback(b: &mut Bencher) {
-            let map: super::Map = vec![vec![Tile::Air]].into();
+            let map = super::Map::try_from(vec![vec![Tile::Air]]).expect("invalid map");
             let coordinate: super::Coordinate = test::black_box((1, 0).into());
 
             b.iter(|| {
@@ -1,11 +1,35 @@
 pub mod coordinate;
 pub mod map;
+#[allow(dead_code)]
+pub mod packed;
+#[allow(dead_code)]
+pub mod render;
+#[allow(dead_code)]
+pub mod route;
+#[allow(dead_code)]
+pub mod search;
 pub mod tile;
 
-use coordinate::Coordinate;
+use coordinate::{
+    Coordinate,
+    Vector,
+};
 use map::Map;
+use thiserror::Error;
 use tile::Tile;
 
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("slope does not move")]
+    ZeroSlope,
+
+    #[error("slope {0:?} never leaves the map as it does not move down or up")]
+    HorizontalSlope(Vector),
+
+    #[error("no moves allowed")]
+    NoMoves,
+}
+
 #[derive(Default)]
 pub struct Toboggan {
     position: Coordinate,
@@ -23,26 +47,84 @@ pub fn new(map: Map) -> Toboggan {
     }
 }
 
+fn check_slope(slope: Vector) -> Result<(), Error> {
+    if slope.is_zero() {
+        return Err(Error::ZeroSlope);
+    }
+
+    if slope.y == 0 {
+        return Err(Error::HorizontalSlope(slope));
+    }
+
+    Ok(())
+}
+
+/// Iterator over the positions a ride visits and their tiles. Ends when the
+/// ride leaves the map at the bottom or top.
+#[derive(Debug, Clone)]
+pub struct Ride<'a> {
+    map: &'a Map,
+    position: Coordinate,
+    slope: Vector,
+}
+
+impl<'a> Ride<'a> {
+    pub fn new(map: &'a Map, start: Coordinate, slope: Vector) -> Result<Self, Error> {
+        check_slope(slope)?;
+
+        Ok(Self {
+            map,
+            position: start,
+            slope,
+        })
+    }
+
+    pub fn trees_seen(self) -> usize {
+        self.filter(|(_, tile)| *tile == Tile::Tree).count()
+    }
+}
+
+impl Iterator for Ride<'_> {
+    type Item = (Coordinate, Tile);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.position + self.slope;
+        let tile = *self.map.get_tile(&position)?;
+
+        self.position = position;
+
+        Some((position, tile))
+    }
+}
+
 impl Toboggan {
-    pub fn ride(&mut self, right: usize, down: usize) {
-        loop {
-            self.step(right, down);
-
-            match self.map.get_tile(&self.position) {
-                Some(tile) => {
-                    if tile == &Tile::Tree {
-                        self.trees_seen += 1;
-                    }
-                }
+    pub fn ride(&mut self, slope: Vector) -> Result<(), Error> {
+        let mut position = self.position;
 
-                // Reached bottom of map
-                None => break,
+        for (visited, tile) in Ride::new(&self.map, self.position, slope)? {
+            if tile == Tile::Tree {
+                self.trees_seen += 1;
             }
+
+            position = visited;
         }
+
+        // Stop where the ride left the map
+        self.position = position + slope;
+
+        Ok(())
     }
 
-    fn step(&mut self, right: usize, down: usize) {
-        self.position = self.position.step_right(right).step_down(down)
+    /// Positions and tiles that riding with the slope would visit, without
+    /// moving the toboggan.
+    #[allow(dead_code)]
+    pub fn steps(&self, slope: Vector) -> Result<Ride<'_>, Error> {
+        Ride::new(&self.map, self.position, slope)
+    }
+
+    #[allow(dead_code)]
+    pub fn map(&self) -> &Map {
+        &self.map
     }
 
     pub fn reset(&mut self) {
@@ -55,17 +137,22 @@ impl Toboggan {
 mod test {
     use super::{
         new,
+        Error,
         Map,
+        Ride,
+        Tile,
     };
 
     mod ride {
+        use super::Tile;
+
         #[test]
         fn input_test_part1() {
             let input = include_str!("../input_test.txt")
                 .parse()
                 .expect("invalid map");
             let mut rider = super::new(input);
-            rider.ride(3, 1);
+            rider.ride((3, 1).into()).expect("invalid slope");
 
             let got = rider.trees_seen;
             let expected = 7;
@@ -89,7 +176,7 @@ mod test {
             let mut rider = super::new(input);
 
             for ((right, down), expected) in slopes {
-                rider.ride(right, down);
+                rider.ride((right, down).into()).expect("invalid slope");
 
                 let got = rider.trees_seen;
 
@@ -103,6 +190,89 @@ mod test {
                 rider.reset()
             }
         }
+
+        #[test]
+        fn left() {
+            let input: super::Map = include_str!("../input_test.txt")
+                .parse()
+                .expect("invalid map");
+
+            let mut right = super::new(input.clone());
+            right.ride((3, 1).into()).expect("invalid slope");
+
+            let mut left = super::new(input);
+            left.ride((-3, 1).into()).expect("invalid slope");
+
+            assert_eq!(7, right.trees_seen);
+            assert_eq!(3, left.trees_seen);
+        }
+
+        #[test]
+        fn up() {
+            let input = include_str!("../input_test.txt")
+                .parse()
+                .expect("invalid map");
+            let mut rider = super::new(input);
+
+            rider.ride((1, -1).into()).expect("invalid slope");
+
+            assert_eq!(0, rider.trees_seen);
+        }
+
+        #[test]
+        fn steps() {
+            let input = include_str!("../input_test.txt")
+                .parse()
+                .expect("invalid map");
+            let rider = super::new(input);
+
+            let expected = vec![
+                ((3, 1).into(), Tile::Air),
+                ((6, 2).into(), Tile::Tree),
+                ((9, 3).into(), Tile::Air),
+            ];
+            let got = rider
+                .steps((3, 1).into())
+                .expect("invalid slope")
+                .take(3)
+                .collect::<Vec<_>>();
+
+            assert_eq!(expected, got);
+            assert_eq!(10, rider.steps((3, 1).into()).unwrap().count());
+        }
+
+        #[test]
+        fn steps_several_slopes() {
+            let input = include_str!("../input_test.txt")
+                .parse()
+                .expect("invalid map");
+            let rider = super::new(input);
+
+            let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+            let got = slopes
+                .into_iter()
+                .map(|slope| rider.steps(slope.into()).map(super::Ride::trees_seen))
+                .product::<Result<usize, _>>();
+
+            assert_eq!(Ok(336), got);
+        }
+
+        #[test]
+        fn zero_slope() {
+            let mut rider = super::new(super::Map::default());
+
+            assert_eq!(Err(super::Error::ZeroSlope), rider.ride((0, 0).into()));
+        }
+
+        #[test]
+        fn horizontal_slope() {
+            let mut rider = super::new(super::Map::default());
+
+            assert_eq!(
+                Err(super::Error::HorizontalSlope((1, 0).into())),
+                rider.ride((1, 0).into())
+            );
+        }
     }
 }
 
@@ -125,7 +295,7 @@ mod bench {
             let mut rider = super::new(input);
 
             b.iter(|| {
-                rider.ride(3, 1);
+                let _ = rider.ride((3, 1).into());
                 rider.reset();
             });
         }
@@ -141,7 +311,7 @@ mod bench {
 
             b.iter(|| {
                 for (right, down) in &slopes {
-                    rider.ride(*right, *down);
+                    let _ = rider.ride((*right, *down).into());
                     rider.reset();
                 }
             });
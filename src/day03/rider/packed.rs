@@ -0,0 +1,254 @@
+use std::convert::{
+    TryFrom,
+    TryInto,
+};
+
+use super::{
+    check_slope,
+    coordinate::{
+        Coordinate,
+        Vector,
+    },
+    map::{
+        Error,
+        Map,
+    },
+    tile::Tile,
+    Error as RideError,
+};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Map that stores one bit per tile in a flat buffer. A set bit is a tree,
+/// every other tile is treated as open. Each row starts at a new word so row
+/// wise popcounts don't need masking between rows.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PackedMap {
+    bits: Vec<u64>,
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+}
+
+impl PackedMap {
+    fn with_size(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+
+        Self {
+            bits: vec![0; words_per_row * height],
+            width,
+            height,
+            words_per_row,
+        }
+    }
+
+    fn set_tree(&mut self, x: usize, y: usize) {
+        self.bits[y * self.words_per_row + x / WORD_BITS] |= 1 << (x % WORD_BITS);
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Wraps horizontally like [`Map`]. Returns `None` below or above the map.
+    pub fn is_tree(&self, coordinate: Coordinate) -> Option<bool> {
+        let y = usize::try_from(coordinate.y)
+            .ok()
+            .filter(|y| *y < self.height)?;
+        let width = isize::try_from(self.width).ok().filter(|w| *w > 0)?;
+        let x = usize::try_from(coordinate.x.rem_euclid(width)).ok()?;
+
+        let word = self.bits[y * self.words_per_row + x / WORD_BITS];
+
+        Some(word >> (x % WORD_BITS) & 1 == 1)
+    }
+
+    pub fn row_trees(&self, y: usize) -> Option<usize> {
+        if y >= self.height {
+            return None;
+        }
+
+        let row = &self.bits[y * self.words_per_row..(y + 1) * self.words_per_row];
+
+        Some(row.iter().map(|word| word.count_ones() as usize).sum())
+    }
+
+    pub fn trees(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn trees_seen(&self, slope: Vector) -> Result<usize, RideError> {
+        Ok(self.trees_seen_many(&[slope])?[0])
+    }
+
+    /// Trees seen for every slope, starting at the top left. All slopes are
+    /// walked together in a single pass over the rows.
+    pub fn trees_seen_many(&self, slopes: &[Vector]) -> Result<Vec<usize>, RideError> {
+        for slope in slopes {
+            check_slope(*slope)?;
+        }
+
+        let mut trees_seen = vec![0; slopes.len()];
+
+        for y in 1..self.height {
+            let row = isize::try_from(y).expect("map too high");
+
+            for (slope, seen) in slopes.iter().zip(trees_seen.iter_mut()) {
+                // Slopes going up leave the map right away
+                if slope.y < 0 || row % slope.y != 0 {
+                    continue;
+                }
+
+                let coordinate = Coordinate {
+                    x: row / slope.y * slope.x,
+                    y: row,
+                };
+
+                if self.is_tree(coordinate) == Some(true) {
+                    *seen += 1;
+                }
+            }
+        }
+
+        Ok(trees_seen)
+    }
+}
+
+impl From<&Map> for PackedMap {
+    fn from(map: &Map) -> Self {
+        let mut packed = Self::with_size(map.width(), map.height());
+
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                let coordinate = (x.try_into().unwrap(), y.try_into().unwrap()).into();
+
+                if map.get_tile(&coordinate) == Some(&Tile::Tree) {
+                    packed.set_tree(x, y);
+                }
+            }
+        }
+
+        packed
+    }
+}
+
+/// Parses the same format as [`Map`] without building the tiles first.
+impl std::str::FromStr for PackedMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let width = s.lines().next().map_or(0, |line| line.chars().count());
+        let height = s.lines().count();
+
+        let mut packed = Self::with_size(width, height);
+
+        for (y, line) in s.lines().enumerate() {
+            let mut got = 0;
+
+            for (x, ch) in line.chars().enumerate() {
+                let tile = Tile::try_from(ch).map_err(|source| Error::InvalidTile {
+                    line: y + 1,
+                    column: x + 1,
+                    source,
+                })?;
+
+                if x < width && tile == Tile::Tree {
+                    packed.set_tree(x, y);
+                }
+
+                got += 1;
+            }
+
+            if got != width {
+                return Err(Error::RaggedLine {
+                    line: y + 1,
+                    expected: width,
+                    got,
+                });
+            }
+        }
+
+        Ok(packed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Map,
+        PackedMap,
+        RideError,
+    };
+
+    const INPUT: &str = include_str!("../input_test.txt");
+
+    #[test]
+    fn from_map() {
+        let map = INPUT.parse::<Map>().expect("invalid map");
+
+        let expected = INPUT.parse::<PackedMap>().expect("invalid map");
+        let got = PackedMap::from(&map);
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn is_tree() {
+        let map = INPUT.parse::<PackedMap>().expect("invalid map");
+
+        assert_eq!(Some(false), map.is_tree((0, 0).into()));
+        assert_eq!(Some(true), map.is_tree((2, 0).into()));
+        assert_eq!(Some(true), map.is_tree((13, 0).into()));
+        assert_eq!(Some(true), map.is_tree((-1, 3).into()));
+        assert_eq!(None, map.is_tree((0, 11).into()));
+    }
+
+    #[test]
+    fn wide_rows() {
+        let line = format!("{}#", ".".repeat(99));
+        let input = format!("{}\n{}", line, line.replace('#', "."));
+        let map = input.parse::<PackedMap>().expect("invalid map");
+
+        assert_eq!(Some(true), map.is_tree((99, 0).into()));
+        assert_eq!(Some(1), map.row_trees(0));
+        assert_eq!(Some(0), map.row_trees(1));
+        assert_eq!(None, map.row_trees(2));
+    }
+
+    #[test]
+    fn trees() {
+        let map = INPUT.parse::<PackedMap>().expect("invalid map");
+        let expected = INPUT.chars().filter(|ch| *ch == '#').count();
+
+        assert_eq!(expected, map.trees());
+        assert_eq!(Some(2), map.row_trees(0));
+    }
+
+    #[test]
+    fn trees_seen_many() {
+        let map = INPUT.parse::<PackedMap>().expect("invalid map");
+        let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2), (1, -1)]
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+
+        let expected = vec![2, 7, 3, 4, 2, 0];
+        let got = map.trees_seen_many(&slopes).expect("invalid slopes");
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn invalid_slope() {
+        let map = INPUT.parse::<PackedMap>().expect("invalid map");
+
+        assert_eq!(Err(RideError::ZeroSlope), map.trees_seen((0, 0).into()));
+    }
+}
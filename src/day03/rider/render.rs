@@ -0,0 +1,198 @@
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    io::Write,
+    ops::Range,
+};
+
+use super::{
+    coordinate::Coordinate,
+    map::Map,
+    tile::Tile,
+};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Cell {
+    Tile(Tile),
+    TreeHit,
+    Clear,
+}
+
+impl From<Cell> for char {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Tile(tile) => tile.into(),
+            Cell::TreeHit => 'O',
+            Cell::Clear => 'X',
+        }
+    }
+}
+
+impl Cell {
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Cell::Tile(Tile::Tree) => [34, 100, 34],
+            Cell::Tile(Tile::Air) => [255, 255, 255],
+            Cell::Tile(Tile::Rock) => [128, 128, 128],
+            Cell::Tile(Tile::Snow) => [200, 230, 255],
+            Cell::TreeHit => [220, 20, 20],
+            Cell::Clear => [20, 20, 220],
+        }
+    }
+}
+
+/// Draws the map repeated horizontally as far as the visited positions reach.
+/// Visited trees are drawn as `O` and visited clear squares as `X`.
+#[derive(Debug)]
+pub struct Render<'a> {
+    map: &'a Map,
+    visited: HashSet<Coordinate>,
+    columns: Range<isize>,
+}
+
+impl<'a> Render<'a> {
+    pub fn new<I: IntoIterator<Item = Coordinate>>(map: &'a Map, visited: I) -> Self {
+        let visited = visited.into_iter().collect::<HashSet<_>>();
+
+        let width = isize::try_from(map.width()).expect("map too wide");
+        let start = visited.iter().map(|c| c.x).min().unwrap_or(0).min(0);
+        let end = visited
+            .iter()
+            .map(|c| c.x + 1)
+            .max()
+            .unwrap_or(0)
+            .max(width);
+
+        Self {
+            map,
+            visited,
+            columns: start..end,
+        }
+    }
+
+    /// Only draw the given columns.
+    pub fn window(mut self, columns: Range<isize>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    fn cell(&self, coordinate: Coordinate) -> Option<Cell> {
+        let tile = *self.map.get_tile(&coordinate)?;
+
+        let cell = match (self.visited.contains(&coordinate), tile) {
+            (true, Tile::Tree) => Cell::TreeHit,
+            (true, _) => Cell::Clear,
+            (false, tile) => Cell::Tile(tile),
+        };
+
+        Some(cell)
+    }
+
+    fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = Cell> + '_> + '_ {
+        let height = isize::try_from(self.map.height()).expect("map too high");
+
+        (0..height).map(move |y| {
+            self.columns
+                .clone()
+                .filter_map(move |x| self.cell((x, y).into()))
+        })
+    }
+
+    /// Writes a binary PPM image with one pixel per tile.
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let width = usize::try_from(self.columns.end - self.columns.start).unwrap_or(0);
+        writeln!(writer, "P6\n{} {}\n255", width, self.map.height())?;
+
+        for row in self.rows() {
+            for cell in row {
+                writer.write_all(&cell.rgb())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Render<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            let line = row.map(char::from).collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Render;
+
+    const INPUT: &str = "..#\n#..\n.#.";
+
+    fn rider() -> crate::day03::rider::Toboggan {
+        crate::day03::rider::new(INPUT.parse().expect("invalid map"))
+    }
+
+    #[test]
+    fn text() {
+        let rider = rider();
+        let path = rider
+            .steps((2, 1).into())
+            .expect("invalid slope")
+            .map(|(position, _)| position);
+
+        let expected = "..#..\n#.X#.\n.#..O\n";
+        let got = Render::new(rider.map(), path).to_string();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn text_left() {
+        let rider = rider();
+        let path = rider
+            .steps((-1, 1).into())
+            .expect("invalid slope")
+            .map(|(position, _)| position);
+
+        let expected = ".#..#\n.X#..\nO..#.\n";
+        let got = Render::new(rider.map(), path).to_string();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn window() {
+        let rider = rider();
+        let path = rider
+            .steps((2, 1).into())
+            .expect("invalid slope")
+            .map(|(position, _)| position);
+
+        let expected = "#..\nX#.\n..O\n";
+        let got = Render::new(rider.map(), path).window(2..5).to_string();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn ppm() {
+        let rider = rider();
+        let path = rider
+            .steps((2, 1).into())
+            .expect("invalid slope")
+            .map(|(position, _)| position);
+
+        let mut got = Vec::new();
+        Render::new(rider.map(), path)
+            .window(4..5)
+            .write_ppm(&mut got)
+            .expect("can not write image");
+
+        let mut expected = b"P6\n1 3\n255\n".to_vec();
+        expected.extend_from_slice(&[255, 255, 255, 255, 255, 255, 220, 20, 20]);
+
+        assert_eq!(expected, got);
+    }
+}
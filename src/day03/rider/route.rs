@@ -0,0 +1,263 @@
+use std::{
+    cmp::Reverse,
+    collections::{
+        BinaryHeap,
+        HashMap,
+    },
+    convert::TryFrom,
+};
+
+use super::{
+    coordinate::{
+        Coordinate,
+        Vector,
+    },
+    map::Map,
+    tile::Tile,
+    Error,
+};
+
+/// Cost of entering a tile. Tiles without a cost are free, the default makes
+/// every tree cost one so a route's cost is the number of trees it hits.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Costs {
+    costs: HashMap<Tile, usize>,
+}
+
+impl Default for Costs {
+    fn default() -> Self {
+        Self::free().with(Tile::Tree, 1)
+    }
+}
+
+impl Costs {
+    pub fn free() -> Self {
+        Self {
+            costs: HashMap::new(),
+        }
+    }
+
+    pub fn with(mut self, tile: Tile, cost: usize) -> Self {
+        self.costs.insert(tile, cost);
+        self
+    }
+
+    pub fn cost(&self, tile: Tile) -> usize {
+        self.costs.get(&tile).copied().unwrap_or(0)
+    }
+
+    fn min(&self) -> usize {
+        [Tile::Tree, Tile::Air, Tile::Rock, Tile::Snow]
+            .iter()
+            .map(|tile| self.cost(*tile))
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// Positions from the top left start to the last position before leaving the
+/// bottom of the map. The `x` of each position is wrapped into the map.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Route {
+    pub path: Vec<Coordinate>,
+    pub cost: usize,
+}
+
+/// Finds the cheapest route from the top left corner out of the bottom of the
+/// map using A*. Like a ride the start tile is free and every entered tile
+/// costs according to `costs`. Returns `None` if the moves can't reach the
+/// bottom or the map has no tiles to move over.
+pub fn find(map: &Map, moves: &[Vector], costs: &Costs) -> Result<Option<Route>, Error> {
+    if moves.is_empty() {
+        return Err(Error::NoMoves);
+    }
+
+    if moves.iter().any(|slope| slope.is_zero()) {
+        return Err(Error::ZeroSlope);
+    }
+
+    let width = isize::try_from(map.width()).expect("map too wide");
+    let height = isize::try_from(map.height()).expect("map too high");
+
+    let max_down = match moves.iter().map(|slope| slope.y).max() {
+        Some(max_down) if max_down > 0 && height > 0 && width > 0 => max_down,
+        _ => return Ok(None),
+    };
+
+    let min_cost = costs.min();
+    // Every move but the one leaving the map enters a tile that costs at least
+    // `min_cost`.
+    let heuristic = |position: Coordinate| {
+        let moves_left = (height - position.y + max_down - 1) / max_down;
+        usize::try_from(moves_left - 1).unwrap_or(0) * min_cost
+    };
+
+    let start = Coordinate::default();
+
+    let mut costs_so_far = HashMap::new();
+    let mut previous = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    costs_so_far.insert(start, 0);
+    queue.push(Reverse((heuristic(start), 0, start)));
+
+    while let Some(Reverse((_, cost, position))) = queue.pop() {
+        if costs_so_far.get(&position).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        if moves.iter().any(|slope| position.y + slope.y >= height) {
+            let mut path = vec![position];
+            while let Some(before) = previous.get(path.last().expect("path is never empty")) {
+                path.push(*before);
+            }
+            path.reverse();
+
+            return Ok(Some(Route { path, cost }));
+        }
+
+        for slope in moves {
+            let next = position + *slope;
+            let next = Coordinate {
+                x: next.x.rem_euclid(width),
+                y: next.y,
+            };
+
+            let tile = match map.get_tile(&next) {
+                Some(tile) => *tile,
+                None => continue,
+            };
+
+            let next_cost = cost + costs.cost(tile);
+
+            if costs_so_far.get(&next).is_none_or(|best| next_cost < *best) {
+                costs_so_far.insert(next, next_cost);
+                previous.insert(next, position);
+                queue.push(Reverse((next_cost + heuristic(next), next_cost, next)));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        find,
+        Coordinate,
+        Costs,
+        Error,
+        Map,
+        Tile,
+        Vector,
+    };
+    use crate::day03::rider::Ride;
+
+    const INPUT: &str = include_str!("../input_test.txt");
+
+    fn moves(moves: &[(isize, isize)]) -> Vec<Vector> {
+        moves.iter().copied().map(Into::into).collect()
+    }
+
+    #[test]
+    fn single_slope_matches_ride() {
+        let map = INPUT.parse::<Map>().expect("invalid map");
+        let slope = (3, 1).into();
+
+        let expected = Ride::new(&map, (0, 0).into(), slope)
+            .expect("invalid slope")
+            .trees_seen();
+        let got = find(&map, &[slope], &Costs::default())
+            .expect("invalid moves")
+            .expect("no route");
+
+        assert_eq!(expected, got.cost);
+        assert_eq!(11, got.path.len());
+    }
+
+    #[test]
+    fn beats_fixed_slopes() {
+        let map = INPUT.parse::<Map>().expect("invalid map");
+        let moves = moves(&[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]);
+
+        let best_slope = moves
+            .iter()
+            .map(|slope| {
+                Ride::new(&map, (0, 0).into(), *slope)
+                    .expect("invalid slope")
+                    .trees_seen()
+            })
+            .min()
+            .unwrap();
+        let got = find(&map, &moves, &Costs::default())
+            .expect("invalid moves")
+            .expect("no route");
+
+        assert!(got.cost <= best_slope);
+        assert_eq!(0, got.cost);
+    }
+
+    #[test]
+    fn path_cost() {
+        let map = INPUT.parse::<Map>().expect("invalid map");
+        let costs = Costs::default().with(Tile::Air, 2);
+
+        let route = find(&map, &moves(&[(1, 1), (-1, 1), (0, 1)]), &costs)
+            .expect("invalid moves")
+            .expect("no route");
+        let got = route
+            .path
+            .iter()
+            .skip(1)
+            .map(|position| costs.cost(*map.get_tile(position).expect("outside of map")))
+            .sum::<usize>();
+
+        assert_eq!(route.cost, got);
+        assert_eq!(Some(&(0, 0).into()), route.path.first());
+        assert_eq!(Some(10), route.path.last().map(|position| position.y));
+    }
+
+    #[test]
+    fn wraps_horizontally() {
+        const INPUT: &str = "...\n.##\n#..";
+        let map = INPUT.parse::<Map>().expect("invalid map");
+
+        let route = find(&map, &moves(&[(-1, 1)]), &Costs::default())
+            .expect("invalid moves")
+            .expect("no route");
+
+        let expected: Vec<Coordinate> = vec![(0, 0).into(), (2, 1).into(), (1, 2).into()];
+
+        assert_eq!(expected, route.path);
+        assert_eq!(1, route.cost);
+    }
+
+    #[test]
+    fn unreachable() {
+        let map = INPUT.parse::<Map>().expect("invalid map");
+
+        let got = find(&map, &moves(&[(1, 0), (1, -1)]), &Costs::default());
+
+        assert_eq!(Ok(None), got);
+    }
+
+    #[test]
+    fn zero_width() {
+        let map = "\n\n".parse::<Map>().expect("invalid map");
+
+        assert_eq!(0, map.width());
+        assert_eq!(Ok(None), find(&map, &moves(&[(1, 1)]), &Costs::default()));
+    }
+
+    #[test]
+    fn invalid_moves() {
+        let map = INPUT.parse::<Map>().expect("invalid map");
+
+        assert_eq!(Err(Error::NoMoves), find(&map, &[], &Costs::default()));
+        assert_eq!(
+            Err(Error::ZeroSlope),
+            find(&map, &moves(&[(0, 0)]), &Costs::default())
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use rayon::prelude::*;
+use std::ops::RangeInclusive;
+
+use super::{
+    coordinate::{
+        Coordinate,
+        Vector,
+    },
+    map::Map,
+    Ride,
+};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SlopeResult {
+    pub slope: Vector,
+    pub trees_seen: usize,
+}
+
+/// Trees seen for every slope in a range of slopes.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Search {
+    results: Vec<SlopeResult>,
+}
+
+impl Search {
+    /// Rides every combination of `right` and `down` in parallel. Only slopes
+    /// going down are ridden, slopes that never leave the map (`down == 0`)
+    /// or leave it upwards on the first step (`down < 0`) are skipped.
+    pub fn new(map: &Map, right: RangeInclusive<isize>, down: RangeInclusive<isize>) -> Self {
+        let slopes = down
+            .flat_map(|down| right.clone().map(move |right| Vector::from((right, down))))
+            .filter(|slope| slope.y > 0)
+            .collect::<Vec<_>>();
+
+        let results = slopes
+            .into_par_iter()
+            .map(|slope| {
+                let trees_seen = Ride::new(map, Coordinate::default(), slope)
+                    .expect("degenerate slopes are filtered")
+                    .trees_seen();
+
+                SlopeResult { slope, trees_seen }
+            })
+            .collect();
+
+        Self { results }
+    }
+
+    pub fn results(&self) -> &[SlopeResult] {
+        &self.results
+    }
+
+    /// Slope with the fewest trees. Ties go to the first slope in the range.
+    pub fn min(&self) -> Option<&SlopeResult> {
+        self.results.iter().min_by_key(|result| result.trees_seen)
+    }
+
+    /// Slope with the most trees. Ties go to the first slope in the range.
+    pub fn max(&self) -> Option<&SlopeResult> {
+        self.results
+            .iter()
+            .rev()
+            .max_by_key(|result| result.trees_seen)
+    }
+
+    /// All results ordered from the fewest to the most trees.
+    pub fn ranked(&self) -> Vec<&SlopeResult> {
+        let mut ranked = self.results.iter().collect::<Vec<_>>();
+        ranked.sort_by_key(|result| result.trees_seen);
+
+        ranked
+    }
+}
+
+/// Ranked table of all slopes.
+impl std::fmt::Display for Search {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:>5} {:>6} {:>6} {:>6}",
+            "rank", "right", "down", "trees"
+        )?;
+
+        for (rank, result) in self.ranked().into_iter().enumerate() {
+            writeln!(
+                f,
+                "{:>5} {:>6} {:>6} {:>6}",
+                rank + 1,
+                result.slope.x,
+                result.slope.y,
+                result.trees_seen
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Map,
+        Search,
+        SlopeResult,
+    };
+
+    fn map() -> Map {
+        include_str!("../input_test.txt")
+            .parse()
+            .expect("invalid map")
+    }
+
+    fn result(right: isize, down: isize, trees_seen: usize) -> SlopeResult {
+        SlopeResult {
+            slope: (right, down).into(),
+            trees_seen,
+        }
+    }
+
+    #[test]
+    fn results() {
+        let search = Search::new(&map(), 1..=7, 1..=1);
+        let got = search
+            .results()
+            .iter()
+            .filter(|result| result.slope.x % 2 == 1)
+            .copied()
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            result(1, 1, 2),
+            result(3, 1, 7),
+            result(5, 1, 3),
+            result(7, 1, 4),
+        ];
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn skips_horizontal() {
+        let got = Search::new(&map(), 0..=1, 0..=1).results().len();
+
+        assert_eq!(2, got);
+    }
+
+    #[test]
+    fn skips_upwards() {
+        let search = Search::new(&map(), 1..=3, -1..=1);
+
+        assert_eq!(3, search.results().len());
+        assert!(search.results().iter().all(|result| result.slope.y == 1));
+        assert_eq!(Some(&result(2, 1, 1)), search.min());
+    }
+
+    #[test]
+    fn min_max() {
+        let search = Search::new(&map(), 1..=7, 1..=2);
+
+        assert_eq!(Some(&result(5, 2, 0)), search.min());
+        assert_eq!(Some(&result(3, 1, 7)), search.max());
+    }
+
+    #[test]
+    fn ranked() {
+        let search = Search::new(&map(), 1..=7, 1..=2);
+        let ranked = search.ranked();
+
+        assert_eq!(search.results().len(), ranked.len());
+        assert!(ranked
+            .windows(2)
+            .all(|pair| pair[0].trees_seen <= pair[1].trees_seen));
+    }
+
+    #[test]
+    fn table() {
+        let expected =
+            " rank  right   down  trees\n    1      1      1      2\n    2      1      2      2\n";
+        let got = Search::new(&map(), 1..=1, 1..=2).to_string();
+
+        assert_eq!(expected, got);
+    }
+}
@@ -1,15 +1,19 @@
+use std::collections::HashMap;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, Eq, PartialEq)]
 pub enum Error {
     #[error("don't know how to parse {0:?}")]
     UnkownTile(char),
 }
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+#[allow(dead_code)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
 pub enum Tile {
     Tree,
     Air,
+    Rock,
+    Snow,
 }
 
 impl std::convert::TryFrom<char> for Tile {
@@ -23,3 +27,77 @@ impl std::convert::TryFrom<char> for Tile {
         }
     }
 }
+
+impl From<Tile> for char {
+    fn from(tile: Tile) -> Self {
+        match tile {
+            Tile::Tree => '#',
+            Tile::Air => '.',
+            Tile::Rock => '^',
+            Tile::Snow => '*',
+        }
+    }
+}
+
+/// Mapping from map characters to tiles. The default only knows `#` and `.`
+/// like the puzzle input.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Tileset {
+    mapping: HashMap<char, Tile>,
+}
+
+impl Default for Tileset {
+    fn default() -> Self {
+        Self::empty().with('#', Tile::Tree).with('.', Tile::Air)
+    }
+}
+
+#[allow(dead_code)]
+impl Tileset {
+    pub fn empty() -> Self {
+        Self {
+            mapping: HashMap::new(),
+        }
+    }
+
+    /// Maps the character to the tile, replacing an existing mapping.
+    pub fn with(mut self, ch: char, tile: Tile) -> Self {
+        self.mapping.insert(ch, tile);
+        self
+    }
+
+    pub fn tile(&self, ch: char) -> Result<Tile, Error> {
+        self.mapping.get(&ch).copied().ok_or(Error::UnkownTile(ch))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Error,
+        Tile,
+        Tileset,
+    };
+
+    #[test]
+    fn default() {
+        let tileset = Tileset::default();
+
+        assert_eq!(Ok(Tile::Tree), tileset.tile('#'));
+        assert_eq!(Ok(Tile::Air), tileset.tile('.'));
+        assert_eq!(Err(Error::UnkownTile('^')), tileset.tile('^'));
+    }
+
+    #[test]
+    fn extra_tiles() {
+        let tileset = Tileset::default()
+            .with('^', Tile::Rock)
+            .with('*', Tile::Snow)
+            .with('.', Tile::Snow);
+
+        assert_eq!(Ok(Tile::Rock), tileset.tile('^'));
+        assert_eq!(Ok(Tile::Snow), tileset.tile('*'));
+        assert_eq!(Ok(Tile::Snow), tileset.tile('.'));
+    }
+}
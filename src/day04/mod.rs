@@ -1,12 +1,21 @@
 use thiserror::Error;
 
+#[allow(dead_code)]
+mod output;
+mod passport;
 mod passports;
-mod passports_strict;
-
+#[allow(dead_code)]
+mod reader;
+mod report;
+mod rules;
+#[allow(dead_code)]
+mod stats;
+
+use passport::Level;
 use passports::Passports;
-use passports_strict::Passports as PassportsStrict;
+use rules::Rules;
 
-#[allow(clippy::empty_enum)]
+#[allow(clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {}
 
@@ -17,16 +26,18 @@ pub fn run() -> Result<(), Error> {
     Ok(())
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_1() -> Result<usize, Error> {
     const INPUT: &str = include_str!("input.txt");
-    let valid_passports = INPUT.parse::<Passports>().unwrap().len();
+    let valid_passports = Passports::parse(INPUT, Level::Presence, &Rules::default()).len();
 
     Ok(valid_passports)
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_2() -> Result<usize, Error> {
     const INPUT: &str = include_str!("input.txt");
-    let valid_passports = INPUT.parse::<PassportsStrict>().unwrap().len();
+    let valid_passports = Passports::parse(INPUT, Level::Strict, &Rules::default()).len();
 
     Ok(valid_passports)
 }
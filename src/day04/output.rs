@@ -0,0 +1,385 @@
+use std::io::{
+    BufRead,
+    Write,
+};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use super::{
+    passport::{
+        self,
+        Level,
+        Passport,
+        Record,
+        UnknownKeys,
+    },
+    reader::{
+        Entry,
+        Reader,
+    },
+    rules::Rules,
+};
+use crate::csv;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("can not read or write records")]
+    Io(#[from] std::io::Error),
+
+    #[error("can not write json")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Format {
+    /// One record per line in canonical key order.
+    Text,
+
+    /// One JSON object per line.
+    Json,
+
+    /// A header and one column per field.
+    Csv,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Counts {
+    pub valid: usize,
+    pub invalid: usize,
+}
+
+/// Invalid record with its raw values in canonical key order.
+#[derive(Debug, Serialize)]
+struct Invalid<'a> {
+    line: usize,
+    error: String,
+    #[serde(serialize_with = "ordered")]
+    fields: Vec<(&'a str, &'a str)>,
+}
+
+/// Valid record whose values don't parse, only written at [`Level::Presence`].
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+struct Raw<'a, 'b> {
+    #[serde(serialize_with = "ordered")]
+    fields: &'b [(&'a str, &'a str)],
+}
+
+fn ordered<S: serde::Serializer>(
+    fields: &[(&str, &str)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(fields.iter().copied())
+}
+
+/// Reads records and writes the ones valid at `level` under `rules` to `valid`
+/// and all others to `invalid`. Valid records are written normalized if their
+/// values parse, which at [`Level::Presence`] they don't have to, invalid ones
+/// keep their raw values but in canonical key order and, for JSON and CSV,
+/// with their first line and the reason they were rejected.
+pub fn write<R, V, I>(
+    reader: R,
+    level: Level,
+    rules: &Rules,
+    format: Format,
+    mut valid: V,
+    mut invalid: I,
+) -> Result<Counts, Error>
+where
+    R: BufRead,
+    V: Write,
+    I: Write,
+{
+    if format == Format::Csv {
+        let keys = rules.keys().join(",");
+        writeln!(valid, "{}", keys)?;
+        writeln!(invalid, "line,{},error", keys)?;
+    }
+
+    let mut counts = Counts::default();
+
+    for entry in Reader::new(reader) {
+        let entry = entry?;
+        let record = Record::parse(&entry.text, rules, UnknownKeys::Reject)
+            .and_then(|record| record.check(level, rules).map(|()| record));
+
+        match record {
+            Ok(record) => {
+                write_valid(&mut valid, format, &entry, &record, rules)?;
+                counts.valid += 1;
+            }
+            Err(e) => {
+                write_invalid(&mut invalid, format, &entry, rules, &e.to_string())?;
+                counts.invalid += 1;
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Writes the typed values if they parse, which they always do above
+/// [`Level::Presence`], and the raw values in canonical key order otherwise.
+fn write_valid<W: Write>(
+    mut writer: W,
+    format: Format,
+    entry: &Entry,
+    record: &Record,
+    rules: &Rules,
+) -> Result<(), Error> {
+    let Ok(passport) = Passport::new(record, Level::Typed, rules) else {
+        return write_raw(
+            writer,
+            format,
+            rules,
+            &passport::canonical(&entry.text, rules),
+        );
+    };
+
+    match format {
+        Format::Text => writeln!(writer, "{}", passport)?,
+        Format::Json => {
+            serde_json::to_writer(&mut writer, &passport)?;
+            writeln!(writer)?;
+        }
+        Format::Csv => {
+            let fields = passport.fields();
+            let fields = fields
+                .iter()
+                .map(|(key, value)| (*key, value.as_str()))
+                .collect::<Vec<_>>();
+
+            writeln!(writer, "{}", csv::row(values(&fields, &rules.keys())))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_raw<W: Write>(
+    mut writer: W,
+    format: Format,
+    rules: &Rules,
+    fields: &[(&str, &str)],
+) -> Result<(), Error> {
+    match format {
+        Format::Text => writeln!(writer, "{}", text(fields))?,
+        Format::Json => {
+            serde_json::to_writer(&mut writer, &Raw { fields })?;
+            writeln!(writer)?;
+        }
+        Format::Csv => writeln!(writer, "{}", csv::row(values(fields, &rules.keys())))?,
+    }
+
+    Ok(())
+}
+
+fn text(fields: &[(&str, &str)]) -> String {
+    fields
+        .iter()
+        .map(|(key, value)| format!("{}:{}", key, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Values in the order of `keys`, empty if missing.
+fn values<'a>(fields: &'a [(&'a str, &'a str)], keys: &[&str]) -> Vec<&'a str> {
+    keys.iter()
+        .map(|key| {
+            fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map_or("", |(_, value)| *value)
+        })
+        .collect()
+}
+
+fn write_invalid<W: Write>(
+    mut writer: W,
+    format: Format,
+    entry: &Entry,
+    rules: &Rules,
+    error: &str,
+) -> Result<(), Error> {
+    let fields = passport::canonical(&entry.text, rules);
+
+    match format {
+        Format::Text => writeln!(writer, "{}", text(&fields))?,
+        Format::Json => {
+            let invalid = Invalid {
+                line: *entry.lines.start(),
+                error: error.to_string(),
+                fields,
+            };
+
+            serde_json::to_writer(&mut writer, &invalid)?;
+            writeln!(writer)?;
+        }
+        Format::Csv => {
+            let line = entry.lines.start().to_string();
+            let row = std::iter::once(line.as_str())
+                .chain(values(&fields, &rules.keys()))
+                .chain(std::iter::once(error));
+
+            writeln!(writer, "{}", csv::row(row))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        write,
+        Counts,
+        Format,
+        Level,
+        Rules,
+    };
+
+    const INPUT: &str = include_str!("input_test.txt");
+
+    fn split(format: Format) -> (String, String) {
+        let mut valid = Vec::new();
+        let mut invalid = Vec::new();
+
+        let counts = write(
+            INPUT.as_bytes(),
+            Level::Strict,
+            &Rules::default(),
+            format,
+            &mut valid,
+            &mut invalid,
+        )
+        .expect("can not write records");
+
+        assert_eq!(
+            Counts {
+                valid: 2,
+                invalid: 2
+            },
+            counts
+        );
+
+        (
+            String::from_utf8(valid).unwrap(),
+            String::from_utf8(invalid).unwrap(),
+        )
+    }
+
+    #[test]
+    fn text() {
+        let (valid, invalid) = split(Format::Text);
+
+        assert_eq!(
+            vec![
+                "byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:860033327 cid:147",
+                "byr:1931 iyr:2013 eyr:2024 hgt:179cm hcl:#ae17e1 ecl:brn pid:760753108",
+            ],
+            valid.lines().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                "byr:1929 iyr:2013 eyr:2023 hcl:#cfa07d ecl:amb pid:028048884 cid:350",
+                "iyr:2011 eyr:2025 hgt:59in hcl:#cfa07d ecl:brn pid:166559648",
+            ],
+            invalid.lines().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn json() {
+        let (valid, invalid) = split(Format::Json);
+
+        assert_eq!(
+            Some(
+                r##"{"byr":1931,"iyr":2013,"eyr":2024,"hgt":"179cm","hcl":"#ae17e1","ecl":"brn","pid":"760753108"}"##
+            ),
+            valid.lines().nth(1)
+        );
+        assert_eq!(
+            Some(
+                r##"{"line":4,"error":"missing field \"hgt\"","fields":{"byr":"1929","iyr":"2013","eyr":"2023","hcl":"#cfa07d","ecl":"amb","pid":"028048884","cid":"350"}}"##
+            ),
+            invalid.lines().next()
+        );
+    }
+
+    #[test]
+    fn presence() {
+        const INPUT: &str = "byr:abc iyr:2013 eyr:2023 hgt:1 hcl:x ecl:y pid:z \
+                             cid:c1\n\npid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 \
+                             hcl:#623a2f";
+
+        let mut valid = Vec::new();
+        let counts = write(
+            INPUT.as_bytes(),
+            Level::Presence,
+            &Rules::default(),
+            Format::Json,
+            &mut valid,
+            std::io::sink(),
+        )
+        .expect("can not write records");
+
+        assert_eq!(
+            Counts {
+                valid: 2,
+                invalid: 0
+            },
+            counts
+        );
+        assert_eq!(
+            vec![
+                r#"{"byr":"abc","iyr":"2013","eyr":"2023","hgt":"1","hcl":"x","ecl":"y","pid":"z","cid":"c1"}"#,
+                r##"{"byr":1980,"iyr":2012,"eyr":2030,"hgt":"74in","hcl":"#623a2f","ecl":"grn","pid":"087499704"}"##,
+            ],
+            String::from_utf8(valid)
+                .unwrap()
+                .lines()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn keeps_cid() {
+        const INPUT: &str =
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f cid:x9";
+
+        let mut valid = Vec::new();
+        write(
+            INPUT.as_bytes(),
+            Level::Strict,
+            &Rules::default(),
+            Format::Text,
+            &mut valid,
+            std::io::sink(),
+        )
+        .expect("can not write records");
+
+        assert_eq!(
+            "byr:1980 iyr:2012 eyr:2030 hgt:74in hcl:#623a2f ecl:grn pid:087499704 cid:x9\n",
+            String::from_utf8(valid).unwrap()
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let (valid, invalid) = split(Format::Csv);
+
+        assert_eq!(
+            vec![
+                "byr,iyr,eyr,hgt,hcl,ecl,pid,cid",
+                "1937,2017,2020,183cm,#fffffd,gry,860033327,147",
+                "1931,2013,2024,179cm,#ae17e1,brn,760753108,",
+            ],
+            valid.lines().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(r#"12,,2011,2025,59in,#cfa07d,brn,166559648,,"missing field ""byr""""#),
+            invalid.lines().nth(2)
+        );
+    }
+}
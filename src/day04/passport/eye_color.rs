@@ -0,0 +1,37 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("invalid eye color {0:?}")]
+    InvalidEyeColor(String),
+}
+
+/// Lowercase color name, which names are allowed is up to the rules.
+#[derive(Debug, Eq, PartialEq)]
+pub struct EyeColor {
+    pub(super) value: String,
+}
+
+impl std::str::FromStr for EyeColor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(Error::InvalidEyeColor(s.into()));
+        }
+
+        Ok(Self { value: s.into() })
+    }
+}
+
+impl std::fmt::Display for EyeColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl serde::Serialize for EyeColor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
@@ -0,0 +1,63 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("input does not start with #")]
+    NotStartWithHash,
+
+    #[error("input color contains invalid character")]
+    InvalidCharacter,
+
+    #[error("input color has no hex digits")]
+    Empty,
+}
+
+/// Hex color, how many digits it has is up to the rules.
+#[derive(Debug, Eq, PartialEq)]
+pub struct HairColor {
+    pub(super) value: String,
+}
+
+impl std::str::FromStr for HairColor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const VALID_CHARS: [char; 17] = [
+            '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '0', 'a', 'b', 'c', 'd', 'e', 'f',
+        ];
+
+        if !s.starts_with('#') {
+            return Err(Error::NotStartWithHash);
+        }
+
+        let value = s
+            .chars()
+            .skip(1)
+            .map(|c| {
+                if VALID_CHARS.contains(&c) {
+                    Ok(c)
+                } else {
+                    Err(Error::InvalidCharacter)
+                }
+            })
+            .collect::<Result<String, _>>()?;
+
+        if value.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        Ok(Self { value })
+    }
+}
+
+impl std::fmt::Display for HairColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.value)
+    }
+}
+
+impl serde::Serialize for HairColor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
@@ -0,0 +1,48 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("height has no unit")]
+    MissingUnit,
+
+    #[error("invalid number")]
+    InvalidNumber(#[from] std::num::ParseIntError),
+}
+
+/// A number followed by its unit, which units are allowed is up to the rules.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Height {
+    pub(super) value: usize,
+    pub(super) unit: String,
+}
+
+impl std::str::FromStr for Height {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (value, unit) = s.split_at(split);
+
+        let value = value.parse()?;
+        if unit.is_empty() {
+            return Err(Error::MissingUnit);
+        }
+
+        Ok(Self {
+            value,
+            unit: unit.into(),
+        })
+    }
+}
+
+impl std::fmt::Display for Height {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+impl serde::Serialize for Height {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
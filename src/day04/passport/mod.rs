@@ -0,0 +1,1107 @@
+mod eye_color;
+mod hair_color;
+mod height;
+mod passport_id;
+
+use std::collections::BTreeMap;
+
+use eye_color::EyeColor;
+use hair_color::HairColor;
+use height::Height;
+use passport_id::PassportID;
+use serde::Serialize;
+use thiserror::Error;
+
+use super::{
+    report::{
+        self,
+        MalformedToken,
+        Problem,
+    },
+    rules::{
+        Rules,
+        Violation,
+    },
+};
+
+/// How thoroughly a record is checked. Every level includes the checks of
+/// the ones before it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Level {
+    /// All required fields are given.
+    Presence,
+
+    /// Every value parses into its type.
+    Typed,
+
+    /// Every value follows the [`Rules`].
+    Strict,
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+#[error("unknown validation level {0:?}")]
+pub struct UnknownLevel(String);
+
+impl std::str::FromStr for Level {
+    type Err = UnknownLevel;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "presence" => Ok(Self::Presence),
+            "typed" => Ok(Self::Typed),
+            "strict" => Ok(Self::Strict),
+            _ => Err(UnknownLevel(s.into())),
+        }
+    }
+}
+
+/// What to do with keys that are not passport fields.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum UnknownKeys {
+    /// The record is invalid.
+    #[default]
+    Reject,
+
+    /// The key is dropped.
+    #[allow(dead_code)]
+    Ignore,
+
+    /// The key is kept in [`Record::extras`].
+    #[allow(dead_code)]
+    Keep,
+}
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("missing field {0:?}")]
+    MissingField(String),
+
+    #[error("field {key:?} has invalid value {value:?}: {reason}")]
+    InvalidField {
+        key: String,
+        value: String,
+        #[source]
+        reason: FieldError,
+    },
+
+    #[error("field {0:?} is given more than once")]
+    DuplicateField(String),
+
+    #[error("unknown key {key:?} with value {value:?}")]
+    UnknownKey { key: String, value: String },
+
+    #[error(transparent)]
+    MalformedToken(#[from] MalformedToken),
+}
+
+/// Why a single field value was rejected.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum FieldError {
+    #[error(transparent)]
+    Number(#[from] std::num::ParseIntError),
+
+    #[error(transparent)]
+    Rule(#[from] Violation),
+
+    #[error(transparent)]
+    Height(#[from] height::Error),
+
+    #[error(transparent)]
+    HairColor(#[from] hair_color::Error),
+
+    #[error(transparent)]
+    EyeColor(#[from] eye_color::Error),
+
+    #[error(transparent)]
+    PassportID(#[from] passport_id::Error),
+}
+
+/// The first problem of a record, values are only checked if the problem
+/// came from [`Rules::problems`].
+impl From<Problem> for Error {
+    fn from(problem: Problem) -> Self {
+        match problem {
+            Problem::MissingField(key) => Error::MissingField(key),
+            Problem::DuplicateField(key) => Error::DuplicateField(key),
+            Problem::UnknownKey { key, value } => Error::UnknownKey { key, value },
+            Problem::MalformedToken(token) => Error::MalformedToken(MalformedToken(token)),
+            Problem::OutOfRange {
+                key,
+                value,
+                allowed,
+            } => Error::InvalidField {
+                key,
+                value,
+                reason: Violation::OutOfRange(allowed).into(),
+            },
+            Problem::InvalidValue { key, value, reason } => Error::InvalidField {
+                key,
+                value,
+                reason: Violation::Invalid(reason).into(),
+            },
+        }
+    }
+}
+
+/// Raw values of a record that has every field its rules require, each given
+/// once.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Record {
+    /// Values of the keys known to the rules.
+    fields: BTreeMap<String, String>,
+
+    extras: BTreeMap<String, String>,
+}
+
+/// Parses a record with the default [`Rules`], rejecting unknown keys.
+impl std::str::FromStr for Record {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, &Rules::default(), UnknownKeys::Reject)
+    }
+}
+
+impl Record {
+    /// Parses a record whose keys are required and known by `rules`, values
+    /// are only checked by [`Record::check`].
+    pub fn parse(s: &str, rules: &Rules, unknown_keys: UnknownKeys) -> Result<Self, Error> {
+        let problems = rules.problems_with(s, unknown_keys, |_, _| None);
+        if let Some(problem) = problems.into_iter().next() {
+            return Err(problem.into());
+        }
+
+        let mut fields = BTreeMap::new();
+        let mut extras = BTreeMap::new();
+
+        for (key, value) in report::tokens(s).filter_map(Result::ok) {
+            let values = if rules.is_known(key) {
+                &mut fields
+            } else if unknown_keys == UnknownKeys::Keep {
+                &mut extras
+            } else {
+                continue;
+            };
+
+            values.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(Record { fields, extras })
+    }
+
+    /// Value of a known or kept unknown key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .get(key)
+            .or_else(|| self.extras.get(key))
+            .map(String::as_str)
+    }
+
+    /// Unknown keys and their values, only filled with [`UnknownKeys::Keep`].
+    #[allow(dead_code)]
+    pub fn extras(&self) -> &BTreeMap<String, String> {
+        &self.extras
+    }
+
+    /// Checks the values at the given level, the first invalid field in
+    /// canonical order is returned.
+    pub fn check(&self, level: Level, rules: &Rules) -> Result<(), Error> {
+        let mut values = self
+            .fields
+            .iter()
+            .chain(&self.extras)
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        sort(&mut values, rules);
+
+        for (key, value) in values {
+            check(key, value, level, rules).map_err(|reason| Error::InvalidField {
+                key: key.to_string(),
+                value: value.to_string(),
+                reason,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Serializes the fields in canonical order, without `cid` if it is missing.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Passport {
+    //(Birth Year)
+    byr: usize,
+    //(Issue Year)
+    iyr: usize,
+    //(Expiration Year)
+    eyr: usize,
+    //(Height)
+    hgt: Height,
+    //(Hair Color)
+    hcl: HairColor,
+    //(Eye Color)
+    ecl: EyeColor,
+    //(Passport ID)
+    pid: PassportID,
+    //(Country ID)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cid: Option<String>,
+}
+
+impl Passport {
+    /// Types the values of a record. They are only checked against `rules`
+    /// at [`Level::Strict`], a passport always has typed values so
+    /// [`Level::Presence`] is treated like [`Level::Typed`].
+    pub fn new(record: &Record, level: Level, rules: &Rules) -> Result<Self, Error> {
+        fn invalid<'a>(key: &'a str, value: &'a str) -> impl FnOnce(FieldError) -> Error + 'a {
+            move |reason| Error::InvalidField {
+                key: key.to_string(),
+                value: value.to_string(),
+                reason,
+            }
+        }
+
+        fn field<T>(record: &Record, key: &str, level: Level, rules: &Rules) -> Result<T, Error>
+        where
+            T: std::str::FromStr,
+            FieldError: From<T::Err>,
+        {
+            let value = record
+                .get(key)
+                .ok_or_else(|| Error::MissingField(key.to_string()))?;
+
+            typed(key, value, level, rules).map_err(invalid(key, value))
+        }
+
+        Ok(Passport {
+            byr: field(record, "byr", level, rules)?,
+            iyr: field(record, "iyr", level, rules)?,
+            eyr: field(record, "eyr", level, rules)?,
+            hgt: field(record, "hgt", level, rules)?,
+            hcl: field(record, "hcl", level, rules)?,
+            ecl: field(record, "ecl", level, rules)?,
+            pid: field(record, "pid", level, rules)?,
+            // Country ids are optional and have no type, the raw value is kept
+            cid: record
+                .get("cid")
+                .map(|cid| {
+                    check("cid", cid, level, rules)
+                        .map(|()| cid.to_string())
+                        .map_err(invalid("cid", cid))
+                })
+                .transpose()?,
+        })
+    }
+
+    /// Keys and values in canonical order, without `cid` if it is missing.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![
+            ("byr", self.byr.to_string()),
+            ("iyr", self.iyr.to_string()),
+            ("eyr", self.eyr.to_string()),
+            ("hgt", self.hgt.to_string()),
+            ("hcl", self.hcl.to_string()),
+            ("ecl", self.ecl.to_string()),
+            ("pid", self.pid.to_string()),
+        ];
+        fields.extend(self.cid.as_ref().map(|cid| ("cid", cid.clone())));
+
+        fields
+    }
+}
+
+/// Writes the record on a single line in canonical key order.
+impl std::fmt::Display for Passport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields = self
+            .fields()
+            .into_iter()
+            .map(|(key, value)| format!("{}:{}", key, value))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", fields.join(" "))
+    }
+}
+
+/// Sorts key value pairs into the canonical order of `rules`, unknown keys
+/// follow sorted by name.
+fn sort(fields: &mut [(&str, &str)], rules: &Rules) {
+    let keys = rules.keys();
+    fields.sort_by_key(|(key, _)| {
+        let position = keys.iter().position(|k| k == key).unwrap_or(keys.len());
+        (position, *key)
+    });
+}
+
+/// Key value pairs of a record sorted into the canonical order of `rules`,
+/// unknown keys follow sorted by name. Malformed tokens are left out.
+pub fn canonical<'a>(s: &'a str, rules: &Rules) -> Vec<(&'a str, &'a str)> {
+    let mut fields = report::tokens(s).filter_map(Result::ok).collect::<Vec<_>>();
+    sort(&mut fields, rules);
+
+    fields
+}
+
+/// Parses a record with [`Level::Strict`] and the default [`Rules`].
+impl std::str::FromStr for Passport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(&s.parse()?, Level::Strict, &Rules::default())
+    }
+}
+
+/// Parses a single value into its type and checks it against `rules` at
+/// [`Level::Strict`].
+fn typed<T>(key: &str, value: &str, level: Level, rules: &Rules) -> Result<T, FieldError>
+where
+    T: std::str::FromStr,
+    FieldError: From<T::Err>,
+{
+    let typed = value.parse()?;
+
+    if level == Level::Strict {
+        rules.check(key, value)?;
+    }
+
+    Ok(typed)
+}
+
+/// Checks a single value at the given level. Values without a type, like
+/// country ids and unknown keys, are only checked against `rules`.
+fn check(key: &str, value: &str, level: Level, rules: &Rules) -> Result<(), FieldError> {
+    match (key, level) {
+        (_, Level::Presence) => Ok(()),
+        ("byr" | "iyr" | "eyr", _) => typed::<usize>(key, value, level, rules).map(drop),
+        ("hgt", _) => typed::<Height>(key, value, level, rules).map(drop),
+        ("hcl", _) => typed::<HairColor>(key, value, level, rules).map(drop),
+        ("ecl", _) => typed::<EyeColor>(key, value, level, rules).map(drop),
+        ("pid", _) => typed::<PassportID>(key, value, level, rules).map(drop),
+        (_, Level::Strict) => Ok(rules.check(key, value)?),
+        (_, Level::Typed) => Ok(()),
+    }
+}
+
+/// All missing fields, invalid values at the given level, duplicate keys,
+/// unknown keys if they are rejected and malformed tokens of a record.
+pub fn problems(s: &str, level: Level, rules: &Rules, unknown_keys: UnknownKeys) -> Vec<Problem> {
+    rules.problems_with(s, unknown_keys, |key, value| {
+        check(key, value, level, rules)
+            .err()
+            .map(|invalid| match invalid {
+                FieldError::Rule(violation) => violation.problem(key, value),
+                invalid => Problem::InvalidValue {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    reason: invalid.to_string(),
+                },
+            })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        canonical,
+        eye_color::EyeColor,
+        hair_color,
+        hair_color::HairColor,
+        height,
+        height::Height,
+        passport_id::PassportID,
+        problems,
+        Error,
+        FieldError,
+        Level,
+        Passport,
+        Problem,
+        Record,
+        Rules,
+        UnknownKeys,
+        Violation,
+    };
+    use std::collections::BTreeMap;
+
+    mod record {
+        use super::{
+            BTreeMap,
+            Error,
+            Level,
+            Record,
+            Rules,
+            UnknownKeys,
+        };
+
+        fn record(fields: &[(&str, &str)]) -> Record {
+            Record {
+                fields: fields
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+                extras: BTreeMap::new(),
+            }
+        }
+
+        #[test]
+        fn part_1_example1() {
+            const INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm";
+
+            let expected = record(&[
+                ("ecl", "gry"),
+                ("pid", "860033327"),
+                ("eyr", "2020"),
+                ("hcl", "#fffffd"),
+                ("byr", "1937"),
+                ("iyr", "2017"),
+                ("cid", "147"),
+                ("hgt", "183cm"),
+            ]);
+
+            let got = INPUT.parse().expect("invalid input");
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_1_example2() {
+            const INPUT: &str = "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929";
+
+            let expected: Result<Record, Error> = Err(Error::MissingField("hgt".into()));
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_1_example3() {
+            const INPUT: &str = "hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm";
+
+            let expected = record(&[
+                ("ecl", "brn"),
+                ("pid", "760753108"),
+                ("eyr", "2024"),
+                ("hcl", "#ae17e1"),
+                ("byr", "1931"),
+                ("iyr", "2013"),
+                ("hgt", "179cm"),
+            ]);
+            let got = INPUT.parse().expect("invalid input");
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_1_example4() {
+            const INPUT: &str = "hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+            let expected: Result<Record, Error> = Err(Error::MissingField("byr".into()));
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn malformed_token() {
+            const INPUT: &str = "hcl:#cfa07d eyr";
+
+            let got = INPUT.parse::<Record>();
+
+            assert!(matches!(got, Err(Error::MalformedToken(_))));
+        }
+
+        #[test]
+        fn unknown_keys() {
+            const INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd nick:rudolph
+byr:1937 iyr:2017 hgt:183cm";
+
+            assert_eq!(
+                Err(Error::UnknownKey {
+                    key: "nick".into(),
+                    value: "rudolph".into(),
+                }),
+                Record::parse(INPUT, &Rules::default(), UnknownKeys::Reject)
+            );
+
+            let ignored = Record::parse(INPUT, &Rules::default(), UnknownKeys::Ignore)
+                .expect("invalid input");
+            assert!(ignored.extras().is_empty());
+
+            let kept =
+                Record::parse(INPUT, &Rules::default(), UnknownKeys::Keep).expect("invalid input");
+            let expected = vec![("nick".to_string(), "rudolph".to_string())]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>();
+            assert_eq!(&expected, kept.extras());
+            assert_eq!(Ok(()), kept.check(Level::Strict, &Rules::default()));
+        }
+
+        #[test]
+        fn duplicate_extra() {
+            const INPUT: &str = "nick:rudolph nick:dasher";
+
+            assert_eq!(
+                Err(Error::DuplicateField("nick".into())),
+                Record::parse(INPUT, &Rules::default(), UnknownKeys::Keep)
+            );
+        }
+
+        #[test]
+        fn check_levels() {
+            const INPUT: &str =
+                "pid:087499704 hgt:190in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+            let rules = Rules::default();
+            let record = INPUT.parse::<Record>().expect("invalid input");
+
+            assert_eq!(Ok(()), record.check(Level::Presence, &rules));
+            assert_eq!(Ok(()), record.check(Level::Typed, &rules));
+            assert!(matches!(
+                record.check(Level::Strict, &rules),
+                Err(Error::InvalidField { key, .. }) if key == "hgt"
+            ));
+
+            let record = INPUT
+                .replace("hgt:190in", "hgt:74in")
+                .replace("ecl:grn", "ecl:zzz")
+                .parse::<Record>()
+                .expect("invalid input");
+
+            assert_eq!(Ok(()), record.check(Level::Typed, &rules));
+            assert!(matches!(
+                record.check(Level::Strict, &rules),
+                Err(Error::InvalidField { key, .. }) if key == "ecl"
+            ));
+
+            let record = INPUT
+                .replace("pid:087499704", "pid:08749970x")
+                .parse::<Record>()
+                .expect("invalid input");
+
+            assert_eq!(Ok(()), record.check(Level::Presence, &rules));
+            assert!(record.check(Level::Typed, &rules).is_err());
+        }
+
+        #[test]
+        fn level_from_str() {
+            assert_eq!(Ok(Level::Presence), "presence".parse());
+            assert_eq!(Ok(Level::Strict), "strict".parse());
+            assert!("lenient".parse::<Level>().is_err());
+        }
+    }
+
+    mod from_str {
+        use super::{
+            hair_color,
+            height,
+            Error,
+            EyeColor,
+            FieldError,
+            HairColor,
+            Height,
+            Passport,
+            PassportID,
+            Violation,
+        };
+
+        #[test]
+        fn part_1_example1() {
+            const INPUT: &str =
+                "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd byr:1937 iyr:2017 cid:147 hgt:183cm";
+
+            let expected = Passport {
+                ecl: EyeColor {
+                    value: "gry".into(),
+                },
+                pid: PassportID {
+                    value: vec![8, 6, 0, 0, 3, 3, 3, 2, 7],
+                },
+                eyr: 2020,
+                hcl: HairColor {
+                    value: "fffffd".into(),
+                },
+                byr: 1937,
+                iyr: 2017,
+                cid: Some("147".into()),
+                hgt: Height {
+                    value: 183,
+                    unit: "cm".into(),
+                },
+            };
+
+            let got = INPUT.parse().expect("invalid input");
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_1_example2() {
+            const INPUT: &str =
+                "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884 hcl:#cfa07d byr:1929";
+
+            let expected: Result<Passport, Error> = Err(Error::MissingField("hgt".into()));
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_1_example3() {
+            const INPUT: &str =
+                "hcl:#ae17e1 iyr:2013 eyr:2024 ecl:brn pid:760753108 byr:1931 hgt:179cm";
+
+            let expected = Passport {
+                ecl: EyeColor {
+                    value: "brn".into(),
+                },
+                pid: PassportID {
+                    value: vec![7, 6, 0, 7, 5, 3, 1, 0, 8],
+                },
+                eyr: 2024,
+                hcl: HairColor {
+                    value: "ae17e1".into(),
+                },
+                byr: 1931,
+                iyr: 2013,
+                cid: None,
+                hgt: Height {
+                    value: 179,
+                    unit: "cm".into(),
+                },
+            };
+            let got = INPUT.parse().expect("invalid input");
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_1_example4() {
+            const INPUT: &str = "hcl:#cfa07d eyr:2025 pid:166559648 iyr:2011 ecl:brn hgt:59in";
+
+            let expected: Result<Passport, Error> = Err(Error::MissingField("byr".into()));
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_2_invalid_example1() {
+            const INPUT: &str =
+                "eyr:1972 cid:100 hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926";
+
+            let expected: Result<Passport, Error> = Err(Error::InvalidField {
+                key: "eyr".into(),
+                value: "1972".into(),
+                reason: FieldError::Rule(Violation::OutOfRange(2020..=2030)),
+            });
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_2_invalid_example2() {
+            const INPUT: &str =
+                "iyr:2019 hcl:#602927 eyr:1967 hgt:170cm ecl:grn pid:012533040 byr:1946";
+
+            let expected: Result<Passport, Error> = Err(Error::InvalidField {
+                key: "eyr".into(),
+                value: "1967".into(),
+                reason: FieldError::Rule(Violation::OutOfRange(2020..=2030)),
+            });
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_2_invalid_example3() {
+            const INPUT: &str =
+                "hcl:dab227 iyr:2012 ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277";
+
+            let expected: Result<Passport, Error> = Err(Error::InvalidField {
+                key: "hcl".into(),
+                value: "dab227".into(),
+                reason: FieldError::HairColor(hair_color::Error::NotStartWithHash),
+            });
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_2_invalid_example4() {
+            const INPUT: &str =
+                "hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 byr:2007";
+
+            let expected: Result<Passport, Error> = Err(Error::InvalidField {
+                key: "byr".into(),
+                value: "2007".into(),
+                reason: FieldError::Rule(Violation::OutOfRange(1920..=2002)),
+            });
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn invalid_height_number() {
+            const INPUT: &str =
+                "pid:087499704 hgt:in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+
+            let reason = "".parse::<usize>().unwrap_err();
+            let expected: Result<Passport, Error> = Err(Error::InvalidField {
+                key: "hgt".into(),
+                value: "in".into(),
+                reason: FieldError::Height(height::Error::InvalidNumber(reason)),
+            });
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn duplicate_field() {
+            const INPUT: &str =
+                "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f byr:1981";
+
+            let expected: Result<Passport, Error> = Err(Error::DuplicateField("byr".into()));
+            let got = INPUT.parse();
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_2_valid_example1() {
+            const INPUT: &str =
+                "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+
+            let expected = Passport {
+                pid: PassportID {
+                    value: vec![0, 8, 7, 4, 9, 9, 7, 0, 4],
+                },
+                hgt: Height {
+                    value: 74,
+                    unit: "in".into(),
+                },
+                ecl: EyeColor {
+                    value: "grn".into(),
+                },
+                iyr: 2012,
+                eyr: 2030,
+                byr: 1980,
+                hcl: HairColor {
+                    value: "623a2f".into(),
+                },
+                cid: None,
+            };
+
+            let got = INPUT.parse().expect("invalid input");
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_2_valid_example2() {
+            const INPUT: &str =
+                "eyr:2029 ecl:blu cid:129 byr:1989 iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm";
+
+            let expected = Passport {
+                eyr: 2029,
+                ecl: EyeColor {
+                    value: "blu".into(),
+                },
+                cid: Some("129".into()),
+                byr: 1989,
+                iyr: 2014,
+                pid: PassportID {
+                    value: vec![8, 9, 6, 0, 5, 6, 5, 3, 9],
+                },
+                hcl: HairColor {
+                    value: "a97842".into(),
+                },
+                hgt: Height {
+                    value: 165,
+                    unit: "cm".into(),
+                },
+            };
+
+            let got = INPUT.parse().expect("invalid input");
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn part_2_valid_example3() {
+            const INPUT: &str =
+                "hcl:#888785 hgt:164cm byr:2001 iyr:2015 cid:88 pid:545766238 ecl:hzl eyr:2022";
+
+            let expected = Passport {
+                hcl: HairColor {
+                    value: "888785".into(),
+                },
+                hgt: Height {
+                    value: 164,
+                    unit: "cm".into(),
+                },
+                byr: 2001,
+                iyr: 2015,
+                cid: Some("88".into()),
+                pid: PassportID {
+                    value: vec![5, 4, 5, 7, 6, 6, 2, 3, 8],
+                },
+                ecl: EyeColor {
+                    value: "hzl".into(),
+                },
+                eyr: 2022,
+            };
+
+            let got = INPUT.parse().expect("invalid input");
+
+            assert_eq!(expected, got)
+        }
+    }
+
+    mod output {
+        use super::{
+            canonical,
+            Passport,
+            Rules,
+        };
+
+        const INPUT: &str = "ecl:gry pid:060033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm";
+
+        #[test]
+        fn display_round_trip() {
+            let passport = INPUT.parse::<Passport>().expect("invalid input");
+
+            let expected =
+                "byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:060033327 cid:147";
+            let got = passport.to_string();
+
+            assert_eq!(expected, got);
+            assert_eq!(Ok(passport), got.parse());
+        }
+
+        #[test]
+        fn json() {
+            let passport = INPUT
+                .replace(" cid:147", "")
+                .parse::<Passport>()
+                .expect("invalid input");
+
+            let expected = r##"{"byr":1937,"iyr":2017,"eyr":2020,"hgt":"183cm","hcl":"#fffffd","ecl":"gry","pid":"060033327"}"##;
+            let got = serde_json::to_string(&passport).expect("can not serialize");
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn canonical_order() {
+            let expected = vec![
+                ("byr", "1"),
+                ("hgt", "2"),
+                ("cid", "3"),
+                ("a", "4"),
+                ("z", "5"),
+            ];
+            let got = canonical("z:5 cid:3 broken a:4 hgt:2\nbyr:1", &Rules::default());
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    mod problems {
+        use super::{
+            problems,
+            Level,
+            Problem,
+            Rules,
+            UnknownKeys,
+        };
+
+        #[test]
+        fn valid() {
+            const INPUT: &str =
+                "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+
+            assert_eq!(
+                Vec::<Problem>::new(),
+                problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Reject)
+            );
+        }
+
+        #[test]
+        fn reports_every_field() {
+            const INPUT: &str =
+                "hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 byr:2007";
+
+            let expected = vec![
+                Problem::OutOfRange {
+                    key: "hgt".into(),
+                    value: "59cm".into(),
+                    allowed: 150..=193,
+                },
+                Problem::InvalidValue {
+                    key: "ecl".into(),
+                    value: "zzz".into(),
+                    reason: "expected one of amb, blu, brn, gry, grn, hzl, oth".into(),
+                },
+                Problem::OutOfRange {
+                    key: "eyr".into(),
+                    value: "2038".into(),
+                    allowed: 2020..=2030,
+                },
+                Problem::InvalidValue {
+                    key: "hcl".into(),
+                    value: "74454a".into(),
+                    reason: "input does not start with #".into(),
+                },
+                Problem::OutOfRange {
+                    key: "iyr".into(),
+                    value: "2023".into(),
+                    allowed: 2010..=2020,
+                },
+                Problem::InvalidValue {
+                    key: "pid".into(),
+                    value: "3556412378".into(),
+                    reason: "has 10 digits but expected 9".into(),
+                },
+                Problem::OutOfRange {
+                    key: "byr".into(),
+                    value: "2007".into(),
+                    allowed: 1920..=2002,
+                },
+            ];
+            let got = problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Reject);
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn duplicate() {
+            const INPUT: &str =
+                "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f byr:1870";
+
+            let expected = vec![
+                Problem::DuplicateField("byr".into()),
+                Problem::OutOfRange {
+                    key: "byr".into(),
+                    value: "1870".into(),
+                    allowed: 1920..=2002,
+                },
+            ];
+            let got = problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Reject);
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn wrong_length() {
+            const INPUT: &str =
+                "pid:08749970 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f0";
+
+            let expected = vec![
+                Problem::InvalidValue {
+                    key: "pid".into(),
+                    value: "08749970".into(),
+                    reason: "has 8 digits but expected 9".into(),
+                },
+                Problem::InvalidValue {
+                    key: "hcl".into(),
+                    value: "#623a2f0".into(),
+                    reason: "does not match ^#[0-9a-f]{6}$".into(),
+                },
+            ];
+            let got = problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Reject);
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn presence() {
+            const INPUT: &str = "iyr:2013 ecl:amb foo:bar eyr:2023 pid:028048884 nocolon hgt:1";
+
+            let expected = vec![
+                Problem::UnknownKey {
+                    key: "foo".into(),
+                    value: "bar".into(),
+                },
+                Problem::MalformedToken("nocolon".into()),
+                Problem::MissingField("byr".into()),
+                Problem::MissingField("hcl".into()),
+            ];
+            let got = problems(
+                INPUT,
+                Level::Presence,
+                &Rules::default(),
+                UnknownKeys::Reject,
+            );
+
+            assert_eq!(expected, got)
+        }
+
+        #[test]
+        fn unknown_keys() {
+            const INPUT: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 \
+                                 hcl:#623a2f nick:rudolph nick:dasher";
+
+            assert_eq!(
+                vec![
+                    Problem::UnknownKey {
+                        key: "nick".into(),
+                        value: "rudolph".into(),
+                    },
+                    Problem::UnknownKey {
+                        key: "nick".into(),
+                        value: "dasher".into(),
+                    },
+                ],
+                problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Reject)
+            );
+            assert_eq!(
+                Vec::<Problem>::new(),
+                problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Ignore)
+            );
+            assert_eq!(
+                vec![Problem::DuplicateField("nick".into())],
+                problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Keep)
+            );
+        }
+
+        #[test]
+        fn invalid_height_and_missing() {
+            const INPUT: &str = "hgt:cm byr:19x0 zzz:1";
+
+            let expected = vec![
+                Problem::InvalidValue {
+                    key: "hgt".into(),
+                    value: "cm".into(),
+                    reason: "invalid number".into(),
+                },
+                Problem::InvalidValue {
+                    key: "byr".into(),
+                    value: "19x0".into(),
+                    reason: "invalid digit found in string".into(),
+                },
+                Problem::UnknownKey {
+                    key: "zzz".into(),
+                    value: "1".into(),
+                },
+                Problem::MissingField("iyr".into()),
+                Problem::MissingField("eyr".into()),
+                Problem::MissingField("hcl".into()),
+                Problem::MissingField("ecl".into()),
+                Problem::MissingField("pid".into()),
+            ];
+            let got = problems(INPUT, Level::Strict, &Rules::default(), UnknownKeys::Reject);
+
+            assert_eq!(expected, got)
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("passport id is empty")]
+    Empty,
+
+    #[error("passport contains invalid digit")]
+    InvalidDigit(std::num::ParseIntError),
+}
+
+/// Digits of the id, how many there have to be is up to the rules.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PassportID {
+    pub(super) value: Vec<usize>,
+}
+
+impl std::str::FromStr for PassportID {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        let value = s
+            .chars()
+            .map(|c| c.to_string().parse().map_err(Error::InvalidDigit))
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        Ok(Self { value })
+    }
+}
+
+impl std::fmt::Display for PassportID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for digit in &self.value {
+            write!(f, "{}", digit)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl serde::Serialize for PassportID {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
@@ -1,209 +1,229 @@
-use thiserror::Error;
-
-use passport::Passport;
-
-#[allow(clippy::empty_enum)]
-#[derive(Debug, Error, Eq, PartialEq)]
-pub enum Error {}
-
+use std::io::BufRead;
+
+use super::{
+    passport::{
+        self,
+        Level,
+        Record,
+        UnknownKeys,
+    },
+    reader::Reader,
+    report::{
+        self,
+        Report,
+    },
+    rules::Rules,
+};
+
+/// Records of a batch that are valid at a level.
 #[derive(Debug, Eq, PartialEq, Default)]
 pub struct Passports {
-    entries: Vec<Passport>,
+    entries: Vec<Record>,
 }
 
-impl std::str::FromStr for Passports {
-    type Err = Error;
+impl Passports {
+    pub fn parse(s: &str, level: Level, rules: &Rules) -> Self {
+        Self::parse_with(s, level, rules, UnknownKeys::Reject)
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let entries = s
-            .split("\n\n")
-            .filter_map(|line| line.parse().ok())
+    pub fn parse_with(s: &str, level: Level, rules: &Rules, unknown_keys: UnknownKeys) -> Self {
+        let entries = report::records(s)
+            .filter_map(|(_, record)| Record::parse(record, rules, unknown_keys).ok())
+            .filter(|record| record.check(level, rules).is_ok())
             .collect::<Vec<_>>();
 
+        Self { entries }
+    }
+
+    /// Like [`Passports::parse_with`] but reads the records one at a time so
+    /// the whole input never has to be in memory.
+    #[allow(dead_code)]
+    pub fn read<R: BufRead>(
+        reader: R,
+        level: Level,
+        rules: &Rules,
+        unknown_keys: UnknownKeys,
+    ) -> std::io::Result<Self> {
+        let mut entries = Vec::new();
+
+        for entry in Reader::new(reader) {
+            let Ok(record) = Record::parse(&entry?.text, rules, unknown_keys) else {
+                continue;
+            };
+
+            if record.check(level, rules).is_ok() {
+                entries.push(record);
+            }
+        }
+
         Ok(Self { entries })
     }
-}
 
-impl Passports {
     pub fn len(&self) -> usize {
         self.entries.len()
     }
+
+    /// Checks every record of the batch and reports all of its problems
+    /// instead of dropping the invalid ones.
+    #[allow(dead_code)]
+    pub fn validate(
+        s: &str,
+        level: Level,
+        rules: &Rules,
+        unknown_keys: UnknownKeys,
+    ) -> Vec<Report> {
+        report::validate(s, |record| {
+            passport::problems(record, level, rules, unknown_keys)
+        })
+    }
+
+    /// Like [`Passports::validate`] but only checks the fields against
+    /// `rules`, without parsing them into the typed model.
+    #[allow(dead_code)]
+    pub fn validate_with(s: &str, rules: &Rules) -> Vec<Report> {
+        report::validate(s, |record| rules.problems(record))
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Passports;
-
-    mod from_str {
-        use super::Passports;
+    use super::{
+        Level,
+        Passports,
+        Report,
+        Rules,
+        UnknownKeys,
+    };
+
+    mod parse {
+        use super::{
+            Level,
+            Passports,
+            Rules,
+            UnknownKeys,
+        };
 
         #[test]
         fn input() {
             const INPUT: &str = include_str!("input_test.txt");
-            let expected = 2;
-            let got = INPUT.parse::<Passports>().unwrap().len();
 
-            assert_eq!(expected, got);
+            assert_eq!(
+                2,
+                Passports::parse(INPUT, Level::Presence, &Rules::default()).len()
+            );
+            assert_eq!(
+                2,
+                Passports::parse(INPUT, Level::Strict, &Rules::default()).len()
+            );
         }
-    }
-}
-
-mod passport {
-    use itertools::Itertools;
-    use thiserror::Error;
 
-    #[derive(Debug, Error, Eq, PartialEq)]
-    pub enum Error {
-        #[error("missing field {0:?}")]
-        MissingField(&'static str),
+        #[test]
+        fn unknown_keys() {
+            const INPUT: &str = include_str!("input_test.txt");
+            let input = INPUT.replace("cid:147", "nick:rudolph");
+
+            assert_eq!(
+                1,
+                Passports::parse(&input, Level::Strict, &Rules::default()).len()
+            );
+            assert_eq!(
+                2,
+                Passports::parse_with(&input, Level::Strict, &Rules::default(), UnknownKeys::Keep)
+                    .len()
+            );
+        }
     }
 
-    #[derive(Debug, Eq, PartialEq)]
-    pub struct Passport {
-        //(Birth Year)
-        byr: String,
-        //(Issue Year)
-        iyr: String,
-        //(Expiration Year)
-        eyr: String,
-        //(Height)
-        hgt: String,
-        //(Hair Color)
-        hcl: String,
-        //(Eye Color)
-        ecl: String,
-        //(Passport ID)
-        pid: String,
-        //(Country ID)
-        cid: Option<String>,
-    }
+    mod read {
+        use super::{
+            Level,
+            Passports,
+            Rules,
+            UnknownKeys,
+        };
 
-    impl std::str::FromStr for Passport {
-        type Err = Error;
-
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            #[derive(Debug, Default)]
-            struct Builder<'a> {
-                byr: Option<&'a str>,
-                iyr: Option<&'a str>,
-                eyr: Option<&'a str>,
-                hgt: Option<&'a str>,
-                hcl: Option<&'a str>,
-                ecl: Option<&'a str>,
-                pid: Option<&'a str>,
-                cid: Option<&'a str>,
-            };
+        #[test]
+        fn matches_parse() {
+            const INPUT: &str = include_str!("input.txt");
+
+            let expected = Passports::parse(INPUT, Level::Strict, &Rules::default()).len();
+            let got = Passports::read(
+                INPUT.as_bytes(),
+                Level::Strict,
+                &Rules::default(),
+                UnknownKeys::Reject,
+            )
+            .expect("can not read input")
+            .len();
 
-            let values = s
-                .split_whitespace()
-                .map(|entry| entry.splitn(2, ':').collect_tuple().unwrap())
-                .collect::<Vec<_>>();
-
-            let mut builder = Builder::default();
-            for (key, value) in values {
-                match key {
-                    "byr" => builder.byr = Some(value),
-                    "iyr" => builder.iyr = Some(value),
-                    "eyr" => builder.eyr = Some(value),
-                    "hgt" => builder.hgt = Some(value),
-                    "hcl" => builder.hcl = Some(value),
-                    "ecl" => builder.ecl = Some(value),
-                    "pid" => builder.pid = Some(value),
-                    "cid" => builder.cid = Some(value),
-
-                    _ => panic!("unkown key {}", key),
-                }
-            }
+            assert_eq!(expected, got);
+        }
 
-            Ok(Passport {
-                byr: builder.byr.ok_or(Error::MissingField("byr"))?.to_string(),
-                iyr: builder.iyr.ok_or(Error::MissingField("iyr"))?.to_string(),
-                eyr: builder.eyr.ok_or(Error::MissingField("eyr"))?.to_string(),
-                hgt: builder.hgt.ok_or(Error::MissingField("hgt"))?.to_string(),
-                hcl: builder.hcl.ok_or(Error::MissingField("hcl"))?.to_string(),
-                ecl: builder.ecl.ok_or(Error::MissingField("ecl"))?.to_string(),
-                pid: builder.pid.ok_or(Error::MissingField("pid"))?.to_string(),
-                cid: builder.cid.map(ToString::to_string),
-            })
+        #[test]
+        fn crlf_and_blank_lines() {
+            const INPUT: &str = include_str!("input_test.txt");
+            let input = INPUT.replace("\n\n", "\n  \n\t\n").replace('\n', "\r\n");
+
+            let got = Passports::read(
+                input.as_bytes(),
+                Level::Presence,
+                &Rules::default(),
+                UnknownKeys::Reject,
+            )
+            .expect("can not read input")
+            .len();
+
+            assert_eq!(2, got);
+            assert_eq!(
+                2,
+                Passports::parse(&input, Level::Presence, &Rules::default()).len()
+            );
         }
     }
 
-    #[cfg(test)]
-    mod test {
+    mod validate {
         use super::{
-            Error,
-            Passport,
+            Level,
+            Passports,
+            Report,
+            Rules,
+            UnknownKeys,
         };
 
-        mod from_str {
-            use super::{
-                Error,
-                Passport,
-            };
-
-            #[test]
-            fn part_1_example1() {
-                const INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
-byr:1937 iyr:2017 cid:147 hgt:183cm";
-
-                let expected = Passport {
-                    ecl: "gry".into(),
-                    pid: "860033327".into(),
-                    eyr: "2020".into(),
-                    hcl: "#fffffd".into(),
-                    byr: "1937".into(),
-                    iyr: "2017".into(),
-                    cid: Some("147".into()),
-                    hgt: "183cm".into(),
-                };
-
-                let got = INPUT.parse().expect("invalid input");
-
-                assert_eq!(expected, got)
-            }
-
-            #[test]
-            fn part_1_example2() {
-                const INPUT: &str = "iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
-hcl:#cfa07d byr:1929";
-
-                let expected: Result<Passport, Error> = Err(Error::MissingField("hgt"));
-                let got = INPUT.parse();
-
-                assert_eq!(expected, got)
-            }
+        #[test]
+        fn input_test() {
+            const INPUT: &str = include_str!("input_test.txt");
 
-            #[test]
-            fn part_1_example3() {
-                const INPUT: &str = "hcl:#ae17e1 iyr:2013
-eyr:2024
-ecl:brn pid:760753108 byr:1931
-hgt:179cm";
-
-                let expected = Passport {
-                    ecl: "brn".into(),
-                    pid: "760753108".into(),
-                    eyr: "2024".into(),
-                    hcl: "#ae17e1".into(),
-                    byr: "1931".into(),
-                    iyr: "2013".into(),
-                    cid: None,
-                    hgt: "179cm".into(),
-                };
-                let got = INPUT.parse().expect("invalid input");
-
-                assert_eq!(expected, got)
-            }
+            let got = Passports::validate(
+                INPUT,
+                Level::Presence,
+                &Rules::default(),
+                UnknownKeys::Reject,
+            );
+
+            assert_eq!(4, got.len());
+            assert_eq!(
+                vec![true, false, true, false],
+                got.iter().map(Report::is_valid).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                Some(&INPUT[got[1].span.clone()]),
+                INPUT.split("\n\n").nth(1)
+            );
+        }
 
-            #[test]
-            fn part_1_example4() {
-                const INPUT: &str = "hcl:#cfa07d eyr:2025 pid:166559648
-iyr:2011 ecl:brn hgt:59in";
+        #[test]
+        fn matches_parse() {
+            const INPUT: &str = include_str!("input.txt");
 
-                let expected: Result<Passport, Error> = Err(Error::MissingField("byr"));
-                let got = INPUT.parse();
+            for level in [Level::Presence, Level::Typed, Level::Strict] {
+                let expected = Passports::parse(INPUT, level, &Rules::default()).len();
+                let got = Passports::validate(INPUT, level, &Rules::default(), UnknownKeys::Reject)
+                    .iter()
+                    .filter(|report| report.is_valid())
+                    .count();
 
-                assert_eq!(expected, got)
+                assert_eq!(expected, got);
             }
         }
     }
@@ -0,0 +1,132 @@
+use std::{
+    io::{
+        BufRead,
+        Lines,
+    },
+    ops::RangeInclusive,
+};
+
+/// A record read from a stream.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Entry {
+    /// Position of the record in the stream, starting at 0.
+    pub index: usize,
+
+    /// First and last line of the record, starting at 1.
+    pub lines: RangeInclusive<usize>,
+
+    /// Lines of the record joined with `\n`.
+    pub text: String,
+}
+
+/// Reads records one at a time. Records are separated by one or more lines
+/// that are empty or only contain whitespace, `\r\n` line endings are
+/// accepted.
+#[derive(Debug)]
+pub struct Reader<R> {
+    lines: Lines<R>,
+    line: usize,
+    index: usize,
+}
+
+impl<R: BufRead> Reader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line: 0,
+            index: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = std::io::Result<Entry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut text = String::new();
+        let mut lines: Option<RangeInclusive<usize>> = None;
+
+        for line in &mut self.lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            self.line += 1;
+
+            if line.trim().is_empty() {
+                if lines.is_some() {
+                    break;
+                }
+
+                continue;
+            }
+
+            lines = match lines {
+                Some(lines) => {
+                    text.push('\n');
+                    Some(*lines.start()..=self.line)
+                }
+                None => Some(self.line..=self.line),
+            };
+            text.push_str(&line);
+        }
+
+        let entry = Entry {
+            index: self.index,
+            lines: lines?,
+            text,
+        };
+        self.index += 1;
+
+        Some(Ok(entry))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Entry,
+        Reader,
+    };
+
+    fn read(input: &str) -> Vec<Entry> {
+        Reader::new(input.as_bytes())
+            .collect::<Result<_, _>>()
+            .expect("can not read input")
+    }
+
+    #[test]
+    fn lines() {
+        let expected = vec![
+            Entry {
+                index: 0,
+                lines: 1..=2,
+                text: "a:1\nb:2".into(),
+            },
+            Entry {
+                index: 1,
+                lines: 6..=6,
+                text: "c:3".into(),
+            },
+        ];
+
+        assert_eq!(expected, read("a:1\nb:2\n\n  \n\t\nc:3\n\n"));
+    }
+
+    #[test]
+    fn crlf() {
+        let got = read("a:1\r\nb:2\r\n \r\nc:3\r\n");
+
+        assert_eq!(
+            vec!["a:1\nb:2", "c:3"],
+            got.iter().map(|e| e.text.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(4..=4, got[1].lines);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Vec::<Entry>::new(), read(""));
+        assert_eq!(Vec::<Entry>::new(), read("\n \n"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::ops::{
+    Range,
+    RangeInclusive,
+};
+use thiserror::Error;
+
+/// Everything that is wrong with a single field or token of a record.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum Problem {
+    #[error("missing field {0:?}")]
+    MissingField(String),
+
+    #[error("field {0:?} is given more than once")]
+    DuplicateField(String),
+
+    #[error("field {key:?} has value {value} outside of {}..={}", allowed.start(), allowed.end())]
+    OutOfRange {
+        key: String,
+        value: String,
+        allowed: RangeInclusive<usize>,
+    },
+
+    #[error("field {key:?} has invalid value {value:?}: {reason}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        reason: String,
+    },
+
+    #[error("unknown key {key:?} with value {value:?}")]
+    UnknownKey { key: String, value: String },
+
+    #[error("token {0:?} is not a key:value pair")]
+    MalformedToken(String),
+}
+
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+#[error("token {0:?} is not a key:value pair")]
+pub struct MalformedToken(pub String);
+
+impl From<MalformedToken> for Problem {
+    fn from(token: MalformedToken) -> Self {
+        Problem::MalformedToken(token.0)
+    }
+}
+
+/// Validation result for one record of a batch.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Report {
+    /// Position of the record in the batch, starting at 0.
+    pub index: usize,
+
+    /// Byte range of the record in the batch input.
+    pub span: Range<usize>,
+
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    #[allow(dead_code)]
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Splits a batch into records and keeps track of where each record is in
+/// the input. Like the streaming reader, records are separated by lines that
+/// are empty or only contain whitespace and `\r\n` line endings are accepted.
+pub fn records(s: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    let mut lines = s.split_inclusive('\n').scan(0, |start, line| {
+        let span = *start..*start + line.trim_end_matches(&['\r', '\n'][..]).len();
+        *start += line.len();
+
+        Some((span, line.trim().is_empty()))
+    });
+
+    std::iter::from_fn(move || {
+        let (first, _) = lines.by_ref().find(|(_, blank)| !blank)?;
+        let mut span = first;
+
+        for (line, blank) in &mut lines {
+            if blank {
+                break;
+            }
+            span.end = line.end;
+        }
+
+        Some((span.clone(), &s[span]))
+    })
+}
+
+/// Key value pairs of a record. Tokens without a `:` are returned as errors
+/// instead of being dropped. This is the only place records are split.
+pub fn tokens(record: &str) -> impl Iterator<Item = Result<(&str, &str), MalformedToken>> {
+    record.split_whitespace().map(|token| {
+        let mut split = token.splitn(2, ':');
+
+        match (split.next(), split.next()) {
+            (Some(key), Some(value)) => Ok((key, value)),
+            _ => Err(MalformedToken(token.to_string())),
+        }
+    })
+}
+
+pub fn validate<F>(s: &str, problems: F) -> Vec<Report>
+where
+    F: Fn(&str) -> Vec<Problem>,
+{
+    records(s)
+        .enumerate()
+        .map(|(index, (span, record))| Report {
+            index,
+            span,
+            problems: problems(record),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        records,
+        tokens,
+        MalformedToken,
+    };
+
+    #[test]
+    fn records_span() {
+        const INPUT: &str = "a:1\nb:2\n\nc:3\n\nd:4\n";
+
+        let got = records(INPUT).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![(0..7, "a:1\nb:2"), (9..12, "c:3"), (14..17, "d:4")],
+            got
+        );
+        for (span, record) in got {
+            assert_eq!(record, &INPUT[span]);
+        }
+    }
+
+    #[test]
+    fn records_crlf_and_blank_lines() {
+        const INPUT: &str = "\r\na:1\r\nb:2\r\n \r\n\t\r\nc:3\r\n";
+
+        assert_eq!(
+            vec![(2..10, "a:1\r\nb:2"), (18..21, "c:3")],
+            records(INPUT).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn tokens_malformed() {
+        let expected = vec![
+            Ok(("a", "1")),
+            Err(MalformedToken("b".into())),
+            Ok(("c", "x:y")),
+        ];
+        let got = tokens("a:1 b\nc:x:y").collect::<Vec<_>>();
+
+        assert_eq!(expected, got);
+    }
+}
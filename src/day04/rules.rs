@@ -0,0 +1,561 @@
+use std::{
+    collections::BTreeMap,
+    ops::RangeInclusive,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use itertools::Itertools;
+use regex::Regex;
+use serde::{
+    Deserialize,
+    Deserializer,
+};
+use thiserror::Error;
+
+use super::{
+    passport::UnknownKeys,
+    report::{
+        self,
+        Problem,
+    },
+};
+
+const DEFAULT: &str = include_str!("rules.toml");
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("can not read rules")]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid toml rules")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("invalid json rules")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unknown rules format {0:?}, expected .toml or .json")]
+    #[allow(dead_code)]
+    UnknownFormat(PathBuf),
+}
+
+/// Why a value breaks a rule.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum Violation {
+    #[error("outside of {}..={}", .0.start(), .0.end())]
+    OutOfRange(RangeInclusive<usize>),
+
+    #[error("has {got} digits but expected {expected}")]
+    WrongLength { expected: usize, got: usize },
+
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl Violation {
+    pub fn problem(self, key: &str, value: &str) -> Problem {
+        match self {
+            Violation::OutOfRange(allowed) => Problem::OutOfRange {
+                key: key.to_string(),
+                value: value.to_string(),
+                allowed,
+            },
+            violation => Problem::InvalidValue {
+                key: key.to_string(),
+                value: value.to_string(),
+                reason: violation.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+pub struct Bounds {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl From<Bounds> for RangeInclusive<usize> {
+    fn from(bounds: Bounds) -> Self {
+        bounds.min..=bounds.max
+    }
+}
+
+/// Constraint on the value of a single field.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Rule {
+    /// A number within the bounds.
+    Range(Bounds),
+
+    /// A number directly followed by one of the units, each with its own
+    /// bounds.
+    Units {
+        units: BTreeMap<String, Bounds>,
+    },
+
+    /// Exactly `count` decimal digits, leading zeros included.
+    Digits {
+        count: usize,
+    },
+
+    Pattern {
+        #[serde(deserialize_with = "pattern")]
+        pattern: Regex,
+    },
+
+    OneOf {
+        values: Vec<String>,
+    },
+}
+
+fn pattern<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Regex, D::Error> {
+    let pattern = String::deserialize(deserializer)?;
+
+    Regex::new(&pattern).map_err(serde::de::Error::custom)
+}
+
+fn number(number: &str, bounds: Bounds) -> Result<(), Violation> {
+    match number.parse::<usize>() {
+        Ok(number) if (bounds.min..=bounds.max).contains(&number) => Ok(()),
+        Ok(_) => Err(Violation::OutOfRange(bounds.into())),
+        Err(e) => Err(Violation::Invalid(e.to_string())),
+    }
+}
+
+impl Rule {
+    pub fn check(&self, value: &str) -> Result<(), Violation> {
+        let reason = match self {
+            Rule::Range(bounds) => return number(value, *bounds),
+            Rule::Units { units } => {
+                let unit = units
+                    .iter()
+                    .find(|(unit, _)| value.ends_with(unit.as_str()));
+
+                match unit {
+                    Some((unit, bounds)) => {
+                        return number(&value[..value.len() - unit.len()], *bounds)
+                    }
+                    None => format!("expected one of the units {}", units.keys().join(", ")),
+                }
+            }
+            Rule::Digits { .. }
+                if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                "expected only digits".to_string()
+            }
+            Rule::Digits { count } if value.len() == *count => return Ok(()),
+            Rule::Digits { count } => {
+                return Err(Violation::WrongLength {
+                    expected: *count,
+                    got: value.len(),
+                })
+            }
+            Rule::Pattern { pattern } if pattern.is_match(value) => return Ok(()),
+            Rule::Pattern { pattern } => format!("does not match {}", pattern),
+            Rule::OneOf { values } if values.iter().any(|v| v == value) => return Ok(()),
+            Rule::OneOf { values } => format!("expected one of {}", values.join(", ")),
+        };
+
+        Err(Violation::Invalid(reason))
+    }
+}
+
+/// Declarative passport rules. The default is the profile used for part 2 and
+/// the only place its ranges, eye colors and id length are defined.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rules {
+    pub required: Vec<String>,
+
+    #[serde(default)]
+    pub optional: Vec<String>,
+
+    #[serde(default)]
+    pub fields: BTreeMap<String, Rule>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::from_toml(DEFAULT).expect("invalid default rules")
+    }
+}
+
+impl Rules {
+    pub fn from_toml(s: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(s)?)
+    }
+
+    #[allow(dead_code)]
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Loads rules from a `.toml` or `.json` file.
+    #[allow(dead_code)]
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&std::fs::read_to_string(path)?),
+            Some("json") => Self::from_json(&std::fs::read_to_string(path)?),
+            _ => Err(Error::UnknownFormat(path.to_path_buf())),
+        }
+    }
+
+    /// Known keys in canonical order, required ones first and keys that only
+    /// have a rule last.
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys = self
+            .required
+            .iter()
+            .chain(&self.optional)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        for key in self.fields.keys() {
+            if !keys.contains(&key.as_str()) {
+                keys.push(key);
+            }
+        }
+
+        keys
+    }
+
+    pub fn is_known(&self, key: &str) -> bool {
+        self.required.iter().any(|k| k == key)
+            || self.optional.iter().any(|k| k == key)
+            || self.fields.contains_key(key)
+    }
+
+    /// Checks a single value, keys without a rule accept any value.
+    pub fn check(&self, key: &str, value: &str) -> Result<(), Violation> {
+        self.fields
+            .get(key)
+            .map_or(Ok(()), |rule| rule.check(value))
+    }
+
+    /// All missing fields, values breaking a rule, duplicate and unknown keys
+    /// and malformed tokens of a record.
+    pub fn problems(&self, record: &str) -> Vec<Problem> {
+        self.problems_with(record, UnknownKeys::Reject, |key, value| {
+            self.check(key, value)
+                .err()
+                .map(|violation| violation.problem(key, value))
+        })
+    }
+
+    /// Like [`Rules::problems`] but every known value, and unknown ones that
+    /// are kept, is checked with `check` instead.
+    pub fn problems_with<F>(
+        &self,
+        record: &str,
+        unknown_keys: UnknownKeys,
+        check: F,
+    ) -> Vec<Problem>
+    where
+        F: Fn(&str, &str) -> Option<Problem>,
+    {
+        let mut present = Vec::new();
+        let mut problems = Vec::new();
+
+        for token in report::tokens(record) {
+            let (key, value) = match token {
+                Ok(token) => token,
+                Err(malformed) => {
+                    problems.push(malformed.into());
+                    continue;
+                }
+            };
+
+            if !self.is_known(key) {
+                match unknown_keys {
+                    UnknownKeys::Reject => {
+                        problems.push(Problem::UnknownKey {
+                            key: key.to_string(),
+                            value: value.to_string(),
+                        });
+                        continue;
+                    }
+                    UnknownKeys::Ignore => continue,
+                    UnknownKeys::Keep => (),
+                }
+            }
+
+            if present.contains(&key) {
+                problems.push(Problem::DuplicateField(key.to_string()));
+            }
+            present.push(key);
+
+            problems.extend(check(key, value));
+        }
+
+        problems.extend(
+            self.required
+                .iter()
+                .filter(|key| !present.contains(&key.as_str()))
+                .map(|key| Problem::MissingField(key.clone())),
+        );
+
+        problems
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Error,
+        Problem,
+        Rules,
+        DEFAULT,
+    };
+
+    const INPUT: &str = include_str!("input.txt");
+
+    mod problems {
+        use super::{
+            Problem,
+            Rules,
+        };
+
+        #[test]
+        fn default_valid() {
+            const INPUT: &str =
+                "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+
+            assert_eq!(Vec::<Problem>::new(), Rules::default().problems(INPUT));
+        }
+
+        #[test]
+        fn default_invalid() {
+            const INPUT: &str =
+                "hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 foo:1 hgt:5ft";
+
+            let expected = vec![
+                Problem::OutOfRange {
+                    key: "hgt".into(),
+                    value: "59cm".into(),
+                    allowed: 150..=193,
+                },
+                Problem::InvalidValue {
+                    key: "ecl".into(),
+                    value: "zzz".into(),
+                    reason: "expected one of amb, blu, brn, gry, grn, hzl, oth".into(),
+                },
+                Problem::OutOfRange {
+                    key: "eyr".into(),
+                    value: "2038".into(),
+                    allowed: 2020..=2030,
+                },
+                Problem::InvalidValue {
+                    key: "hcl".into(),
+                    value: "74454a".into(),
+                    reason: "does not match ^#[0-9a-f]{6}$".into(),
+                },
+                Problem::OutOfRange {
+                    key: "iyr".into(),
+                    value: "2023".into(),
+                    allowed: 2010..=2020,
+                },
+                Problem::InvalidValue {
+                    key: "pid".into(),
+                    value: "3556412378".into(),
+                    reason: "has 10 digits but expected 9".into(),
+                },
+                Problem::UnknownKey {
+                    key: "foo".into(),
+                    value: "1".into(),
+                },
+                Problem::DuplicateField("hgt".into()),
+                Problem::InvalidValue {
+                    key: "hgt".into(),
+                    value: "5ft".into(),
+                    reason: "expected one of the units cm, in".into(),
+                },
+                Problem::MissingField("byr".into()),
+            ];
+            let got = Rules::default().problems(INPUT);
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn custom() {
+            const RULES: &str = r#"{
+                "required": ["name", "age"],
+                "fields": {
+                    "age": { "kind": "range", "min": 18, "max": 99 },
+                    "id": { "kind": "pattern", "pattern": "^[A-Z]{2}[0-9]+$" }
+                }
+            }"#;
+            let rules = Rules::from_json(RULES).expect("invalid rules");
+
+            assert_eq!(
+                Vec::<Problem>::new(),
+                rules.problems("name:santa age:42 id:NP1")
+            );
+            assert_eq!(
+                vec![
+                    Problem::OutOfRange {
+                        key: "age".into(),
+                        value: "17".into(),
+                        allowed: 18..=99,
+                    },
+                    Problem::UnknownKey {
+                        key: "cid".into(),
+                        value: "1".into(),
+                    },
+                    Problem::MissingField("name".into()),
+                ],
+                rules.problems("age:17 cid:1")
+            );
+        }
+    }
+
+    #[test]
+    fn strict_uses_rules() {
+        use crate::day04::{
+            output,
+            passport::Level,
+            passports::Passports,
+            stats::Stats,
+        };
+
+        const RECORD: &str =
+            "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2038 byr:1980 hcl:#623a2f";
+        let default = Rules::default();
+        let rules =
+            Rules::from_toml(&DEFAULT.replace("max = 2030", "max = 2040")).expect("invalid rules");
+
+        assert_eq!(0, Passports::parse(RECORD, Level::Strict, &default).len());
+        assert_eq!(1, Passports::parse(RECORD, Level::Strict, &rules).len());
+        assert_eq!(0, Stats::new(RECORD, Level::Strict, &default).valid);
+        assert_eq!(1, Stats::new(RECORD, Level::Strict, &rules).valid);
+
+        let counts = output::write(
+            RECORD.as_bytes(),
+            Level::Strict,
+            &rules,
+            output::Format::Text,
+            std::io::sink(),
+            std::io::sink(),
+        )
+        .expect("can not write records");
+        assert_eq!(1, counts.valid);
+    }
+
+    #[test]
+    fn custom_required() {
+        use crate::day04::{
+            output,
+            passport::{
+                Level,
+                UnknownKeys,
+            },
+            passports::Passports,
+            stats::Stats,
+        };
+
+        const INPUT: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 \
+                             hcl:#623a2f cid:147
+
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+        let rules = Rules::from_toml(&DEFAULT.replace(
+            "\"pid\"]\noptional = [\"cid\"]",
+            "\"pid\", \"cid\"]\noptional = []",
+        ))
+        .expect("invalid rules");
+
+        for level in [Level::Presence, Level::Typed, Level::Strict] {
+            let valid = Passports::validate(INPUT, level, &rules, UnknownKeys::Reject)
+                .iter()
+                .filter(|report| report.is_valid())
+                .count();
+            let counts = output::write(
+                INPUT.as_bytes(),
+                level,
+                &rules,
+                output::Format::Text,
+                std::io::sink(),
+                std::io::sink(),
+            )
+            .expect("can not write records");
+
+            assert_eq!(1, Passports::parse(INPUT, level, &rules).len());
+            assert_eq!(1, valid);
+            assert_eq!(1, counts.valid);
+            assert_eq!(1, Stats::new(INPUT, level, &rules).valid);
+        }
+    }
+
+    #[test]
+    fn custom_units() {
+        use crate::day04::{
+            passport::Level,
+            passports::Passports,
+        };
+
+        const RECORD: &str =
+            "pid:087499704 hgt:1700mm ecl:grn iyr:2012 eyr:2030 byr:1980 hcl:#623a2f";
+        let rules = Rules::from_toml(&DEFAULT.replace(
+            "units = { cm = { min = 150, max = 193 }, in = { min = 59, max = 76 } }",
+            "units = { mm = { min = 1500, max = 1930 } }",
+        ))
+        .expect("invalid rules");
+
+        assert_eq!(1, Passports::parse(RECORD, Level::Strict, &rules).len());
+        assert_eq!(
+            0,
+            Passports::parse(&RECORD.replace("1700mm", "67in"), Level::Strict, &rules).len()
+        );
+        assert_eq!(
+            0,
+            Passports::parse(RECORD, Level::Strict, &Rules::default()).len()
+        );
+    }
+
+    #[test]
+    fn input_matches_validate() {
+        use crate::day04::{
+            passport::{
+                Level,
+                UnknownKeys,
+            },
+            passports::Passports,
+        };
+
+        let rules = Rules::default();
+        let expected = Passports::parse(INPUT, Level::Strict, &rules).len();
+        let got = Passports::validate(INPUT, Level::Strict, &rules, UnknownKeys::Reject)
+            .iter()
+            .filter(|report| report.is_valid())
+            .count();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn load() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/day04/rules.toml");
+        let rules = Rules::load(path).expect("invalid rules");
+
+        assert_eq!(7, rules.required.len());
+        assert!(matches!(
+            Rules::load("rules.yaml"),
+            Err(Error::UnknownFormat(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        const RULES: &str = r#"
+            required = []
+
+            [fields.pid]
+            kind = "pattern"
+            pattern = "[0-9"
+        "#;
+
+        assert!(matches!(Rules::from_toml(RULES), Err(Error::Toml(_))));
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::{
+    passport::{
+        self,
+        Level,
+        UnknownKeys,
+    },
+    report::{
+        self,
+        Problem,
+    },
+    rules::{
+        Rule,
+        Rules,
+    },
+};
+
+/// Aggregates over every record of a batch, valid or not.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    pub records: usize,
+    pub valid: usize,
+
+    /// Number of records missing each required field.
+    pub missing: BTreeMap<String, usize>,
+
+    pub eye_colors: BTreeMap<String, usize>,
+    pub birth_years: BTreeMap<usize, usize>,
+    pub issue_years: BTreeMap<usize, usize>,
+    pub expiration_years: BTreeMap<usize, usize>,
+
+    /// Heights by unit, `other` for heights without a unit of the rules.
+    pub height_units: BTreeMap<String, usize>,
+
+    /// Problems by kind and field, independent of the values.
+    pub failures: BTreeMap<String, usize>,
+
+    /// Required fields and height units of the rules, listed in the summary
+    /// even if no record has them.
+    #[serde(skip)]
+    required: Vec<String>,
+    #[serde(skip)]
+    units: Vec<String>,
+}
+
+/// Units the rules allow for heights, none if `hgt` has no unit rule.
+fn units(rules: &Rules) -> Vec<&str> {
+    match rules.fields.get("hgt") {
+        Some(Rule::Units { units }) => units.keys().map(String::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn failure(problem: &Problem) -> String {
+    match problem {
+        Problem::MissingField(key) => format!("missing {}", key),
+        Problem::DuplicateField(key) => format!("duplicate {}", key),
+        Problem::OutOfRange { key, .. } => format!("{} out of range", key),
+        Problem::InvalidValue { key, .. } => format!("invalid {}", key),
+        Problem::UnknownKey { key, .. } => format!("unknown key {}", key),
+        Problem::MalformedToken(_) => "malformed token".to_string(),
+    }
+}
+
+impl Stats {
+    pub fn new(s: &str, level: Level, rules: &Rules) -> Self {
+        let mut stats = Self {
+            required: rules.required.clone(),
+            units: units(rules).into_iter().map(String::from).collect(),
+            ..Self::default()
+        };
+
+        for (_, record) in report::records(s) {
+            stats.add(record, level, rules);
+        }
+
+        stats
+    }
+
+    /// Adds a single record, unknown keys count as failures.
+    pub fn add(&mut self, record: &str, level: Level, rules: &Rules) {
+        self.records += 1;
+
+        let units = units(rules);
+        let problems = passport::problems(record, level, rules, UnknownKeys::Reject);
+        if problems.is_empty() {
+            self.valid += 1;
+        }
+
+        for problem in &problems {
+            if let Problem::MissingField(key) = problem {
+                *self.missing.entry(key.clone()).or_default() += 1;
+            }
+
+            *self.failures.entry(failure(problem)).or_default() += 1;
+        }
+
+        for (key, value) in report::tokens(record).filter_map(Result::ok) {
+            let years = match key {
+                "byr" => &mut self.birth_years,
+                "iyr" => &mut self.issue_years,
+                "eyr" => &mut self.expiration_years,
+                "ecl" => {
+                    *self.eye_colors.entry(value.to_string()).or_default() += 1;
+                    continue;
+                }
+                "hgt" => {
+                    let unit = units
+                        .iter()
+                        .find(|unit| value.ends_with(*unit))
+                        .unwrap_or(&"other");
+                    *self.height_units.entry(unit.to_string()).or_default() += 1;
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if let Ok(year) = value.parse() {
+                *years.entry(year).or_default() += 1;
+            }
+        }
+    }
+
+    /// The `n` most common failures, ties sorted by name.
+    pub fn most_common_failures(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut failures = self
+            .failures
+            .iter()
+            .map(|(failure, count)| (failure.as_str(), *count))
+            .collect::<Vec<_>>();
+        failures.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        failures.truncate(n);
+
+        failures
+    }
+}
+
+fn section<K: std::fmt::Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    title: &str,
+    rows: impl IntoIterator<Item = (K, usize)>,
+) -> std::fmt::Result {
+    writeln!(f, "{}", title)?;
+    for (key, count) in rows {
+        writeln!(f, "  {:<20} {:>6}", key, count)?;
+    }
+
+    Ok(())
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:<22} {:>6}", "records", self.records)?;
+        writeln!(f, "{:<22} {:>6}", "valid", self.valid)?;
+
+        let missing = self
+            .required
+            .iter()
+            .map(|key| (key, self.missing.get(key).copied().unwrap_or(0)));
+        section(f, "missing", missing)?;
+        section(
+            f,
+            "eye colors",
+            self.eye_colors.iter().map(|(k, v)| (k, *v)),
+        )?;
+        let units = self
+            .units
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once("other"))
+            .map(|unit| (unit, self.height_units.get(unit).copied().unwrap_or(0)));
+        section(f, "height units", units)?;
+        section(
+            f,
+            "birth years",
+            self.birth_years.iter().map(|(k, v)| (k, *v)),
+        )?;
+        section(
+            f,
+            "issue years",
+            self.issue_years.iter().map(|(k, v)| (k, *v)),
+        )?;
+        section(
+            f,
+            "expiration years",
+            self.expiration_years.iter().map(|(k, v)| (k, *v)),
+        )?;
+        section(f, "most common failures", self.most_common_failures(10))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Level,
+        Rules,
+        Stats,
+    };
+
+    const INPUT: &str = include_str!("input_test.txt");
+
+    #[test]
+    fn input_test() {
+        let stats = Stats::new(INPUT, Level::Strict, &Rules::default());
+
+        assert_eq!(4, stats.records);
+        assert_eq!(2, stats.valid);
+        assert_eq!(Some(&1), stats.missing.get("hgt"));
+        assert_eq!(Some(&1), stats.missing.get("byr"));
+        assert_eq!(None, stats.missing.get("pid"));
+        assert_eq!(Some(&2), stats.eye_colors.get("brn"));
+        assert_eq!(Some(&2), stats.height_units.get("cm"));
+        assert_eq!(Some(&1), stats.height_units.get("in"));
+        assert_eq!(Some(&2), stats.issue_years.get(&2013));
+        assert_eq!(
+            vec![("missing byr", 1), ("missing hgt", 1)],
+            stats.most_common_failures(5)
+        );
+    }
+
+    #[test]
+    fn failures_by_kind() {
+        let input = "hgt:59cm ecl:zzz eyr:2038 hcl:74454a iyr:2023 pid:3556412378 \
+                     byr:2007\n\nhgt:170 ecl:grn eyr:2038 hcl:#74454a iyr:2013 pid:355641237 \
+                     byr:2000 x:1";
+        let stats = Stats::new(input, Level::Strict, &Rules::default());
+
+        assert_eq!(0, stats.valid);
+        assert_eq!(Some(&1), stats.height_units.get("other"));
+        assert_eq!(
+            vec![("eyr out of range", 2), ("byr out of range", 1)],
+            stats.most_common_failures(2)
+        );
+        assert_eq!(Some(&1), stats.failures.get("unknown key x"));
+    }
+
+    #[test]
+    fn summary() {
+        let stats = Stats::new(INPUT, Level::Presence, &Rules::default());
+        let got = stats.to_string();
+
+        assert!(got.starts_with("records                     4\nvalid                       2\n"));
+        assert!(got.contains("  hgt                       1\n"));
+
+        let json = serde_json::to_value(&stats).expect("can not serialize");
+        assert_eq!(json["eye_colors"]["brn"], 2);
+    }
+
+    #[test]
+    fn custom_rules() {
+        const RULES: &str = r#"
+            required = ["hgt", "name"]
+
+            [fields.hgt]
+            kind = "units"
+            units = { mm = { min = 1500, max = 1930 } }
+        "#;
+        let rules = Rules::from_toml(RULES).expect("invalid rules");
+        let stats = Stats::new("hgt:1700mm\n\nhgt:67in name:rudolph", Level::Strict, &rules);
+        let got = stats.to_string();
+
+        assert_eq!(Some(&1), stats.height_units.get("mm"));
+        assert_eq!(Some(&1), stats.height_units.get("other"));
+        assert!(
+            got.contains("missing\n  hgt                       0\n  name                      1\n")
+        );
+        assert!(got.contains(
+            "height units\n  mm                        1\n  other                     1\n"
+        ));
+    }
+}
@@ -4,7 +4,7 @@ mod seating;
 
 use seating::Seat;
 
-#[allow(clippy::empty_enum)]
+#[allow(clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {}
 
@@ -15,6 +15,7 @@ pub fn run() -> Result<(), Error> {
     Ok(())
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_1() -> Result<usize, Error> {
     let max_id = include_str!("input.txt")
         .lines()
@@ -25,7 +26,7 @@ pub fn part_1() -> Result<usize, Error> {
     Ok(max_id)
 }
 
-#[allow(dead_code)]
+#[allow(dead_code, clippy::unnecessary_wraps)]
 pub fn part_2() -> Result<usize, Error> {
     let mut seats = include_str!("input.txt")
         .lines()
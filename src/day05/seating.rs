@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[allow(clippy::empty_enum)]
+#[allow(dead_code, clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {}
 
@@ -18,7 +18,7 @@ impl From<&str> for Seat {
         let column = get_column(s);
         let id = (row * 8) + column;
 
-        Self { row, column, id }
+        Self { id, row, column }
     }
 }
 
@@ -21,7 +21,7 @@ pub fn count_yes_all(s: &str) -> usize {
             group
                 .lines()
                 .map(|line| line.chars().collect::<HashSet<_>>())
-                .fold_first(|set1, set2| set1.bitand(&set2))
+                .reduce(|set1, set2| set1.bitand(&set2))
                 .map_or(0, |group| group.len())
         })
         .sum()
@@ -2,7 +2,7 @@ use thiserror::Error;
 
 mod answers;
 
-#[allow(clippy::empty_enum)]
+#[allow(clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {}
 
@@ -13,12 +13,14 @@ pub fn run() -> Result<(), Error> {
     Ok(())
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_1() -> Result<usize, Error> {
     let count = answers::count_yes(include_str!("input.txt"));
 
     Ok(count)
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_2() -> Result<usize, Error> {
     let count = answers::count_yes_all(include_str!("input.txt"));
 
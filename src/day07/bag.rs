@@ -1,10 +1,11 @@
+use itertools::Itertools;
 use std::collections::{
     HashMap,
     HashSet,
 };
 use thiserror::Error;
 
-#[allow(clippy::empty_enum)]
+#[allow(clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("invalid input")]
@@ -15,6 +16,10 @@ pub enum Error {
 pub struct Bags(Vec<Bag>);
 
 impl Bags {
+    pub fn iter(&self) -> impl Iterator<Item = &Bag> {
+        self.0.iter()
+    }
+
     fn find_containers(&self, for_bag_color: &str) -> Vec<&Bag> {
         self.0
             .iter()
@@ -82,12 +87,62 @@ impl From<Vec<Bag>> for Bags {
     }
 }
 
+impl std::fmt::Display for Bags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for bag in &self.0 {
+            writeln!(f, "{}", bag)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct Bag {
     color: String,
     can_contain: HashMap<String, usize>,
 }
 
+impl Bag {
+    pub fn new(color: impl Into<String>, can_contain: HashMap<String, usize>) -> Self {
+        Self {
+            color: color.into(),
+            can_contain,
+        }
+    }
+
+    pub fn color(&self) -> &str {
+        &self.color
+    }
+
+    pub fn can_contain(&self) -> &HashMap<String, usize> {
+        &self.can_contain
+    }
+}
+
+/// Writes the canonical rule with the contained bags sorted by color.
+impl std::fmt::Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bags contain ", self.color)?;
+
+        if self.can_contain.is_empty() {
+            return write!(f, "no other bags.");
+        }
+
+        let contains = self
+            .can_contain
+            .iter()
+            .sorted()
+            .map(|(color, count)| {
+                let bags = if *count == 1 { "bag" } else { "bags" };
+                format!("{} {} {}", count, color, bags)
+            })
+            .join(", ");
+
+        write!(f, "{}.", contains)
+    }
+}
+
 impl std::str::FromStr for Bag {
     type Err = Error;
 
@@ -102,10 +157,7 @@ impl std::str::FromStr for Bag {
                     .map(str::trim)
                     .map(|s| s.split_ascii_whitespace().collect::<Vec<_>>())
                     .filter_map(|v| match v.as_slice() {
-                        [amount, modifier_color @ .., "bag"]
-                        | [amount, modifier_color @ .., "bag."]
-                        | [amount, modifier_color @ .., "bags."]
-                        | [amount, modifier_color @ .., "bags"] => {
+                        [amount, modifier_color @ .., "bag" | "bag." | "bags." | "bags"] => {
                             if amount == &"no" {
                                 None
                             } else {
@@ -275,6 +327,52 @@ mod test {
         }
     }
 
+    mod display {
+        const INPUT: &str = include_str!("input_example.txt");
+
+        #[test]
+        fn bag() {
+            let expected = "light red bags contain 1 bright white bag, 2 muted yellow bags.";
+            let got = super::Bag {
+                color: "light red".into(),
+                can_contain: vec![("muted yellow".into(), 2), ("bright white".into(), 1)]
+                    .into_iter()
+                    .collect(),
+            }
+            .to_string();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn bag_empty() {
+            let expected = "faded blue bags contain no other bags.";
+            let got = super::Bag {
+                color: "faded blue".into(),
+                can_contain: vec![].into_iter().collect(),
+            }
+            .to_string();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn normalized() {
+            let expected = super::Bags::from(INPUT).to_string();
+            let got = super::Bags::from(expected.as_str()).to_string();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn round_trip() {
+            let expected = super::Bags::from(include_str!("input.txt"));
+            let got = super::Bags::from(expected.to_string().as_str());
+
+            assert_eq!(expected, got);
+        }
+    }
+
     mod bag_from_str {
         #[test]
         fn example_line_0() {
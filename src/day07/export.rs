@@ -0,0 +1,324 @@
+use serde::Serialize;
+use std::{
+    collections::{
+        BTreeSet,
+        HashMap,
+        HashSet,
+        VecDeque,
+    },
+    io::Write,
+};
+use thiserror::Error;
+
+use super::bag::Bags;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("can not write graph: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("can not serialize graph: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Which part of the rules should end up in the exported graph.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Scope<'a> {
+    All,
+
+    /// The color and every bag it must eventually contain.
+    ReachableFrom(&'a str),
+
+    /// The color and every bag that can eventually contain it.
+    ReachableTo(&'a str),
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Graph {
+    pub nodes: Vec<String>,
+    pub edges: Vec<Edge>,
+}
+
+/// The start and every color reachable from it over the edges `build` adds.
+/// Colors are visited once, so cyclic rules terminate.
+fn reachable<'a, F>(start: &'a str, build: F) -> BTreeSet<String>
+where
+    F: FnOnce(&mut HashMap<&'a str, Vec<&'a str>>),
+{
+    let mut adjacent = HashMap::new();
+    build(&mut adjacent);
+
+    let mut visited = HashSet::new();
+    visited.insert(start);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(color) = queue.pop_front() {
+        for next in adjacent.get(color).into_iter().flatten() {
+            if visited.insert(*next) {
+                queue.push_back(*next);
+            }
+        }
+    }
+
+    visited.into_iter().map(ToString::to_string).collect()
+}
+
+/// Quotes a DOT ID, only `"` and `\` have to be escaped inside the quotes.
+fn id(s: &str) -> String {
+    let mut id = String::with_capacity(s.len() + 2);
+    id.push('"');
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            id.push('\\');
+        }
+        id.push(c);
+    }
+    id.push('"');
+
+    id
+}
+
+impl Graph {
+    pub fn new(bags: &Bags, scope: Scope<'_>) -> Self {
+        let nodes = match scope {
+            Scope::All => bags
+                .iter()
+                .flat_map(|bag| {
+                    std::iter::once(bag.color().to_string())
+                        .chain(bag.can_contain().keys().cloned())
+                })
+                .collect::<BTreeSet<_>>(),
+
+            Scope::ReachableFrom(color) => reachable(color, |adjacent| {
+                for bag in bags.iter() {
+                    adjacent
+                        .entry(bag.color())
+                        .or_default()
+                        .extend(bag.can_contain().keys().map(String::as_str));
+                }
+            }),
+
+            Scope::ReachableTo(color) => reachable(color, |adjacent| {
+                for bag in bags.iter() {
+                    for contained in bag.can_contain().keys() {
+                        adjacent
+                            .entry(contained.as_str())
+                            .or_default()
+                            .push(bag.color());
+                    }
+                }
+            }),
+        };
+
+        let mut edges = bags
+            .iter()
+            .filter(|bag| nodes.contains(bag.color()))
+            .flat_map(|bag| {
+                bag.can_contain()
+                    .iter()
+                    .filter(|(color, _)| nodes.contains(*color))
+                    .map(move |(color, count)| Edge {
+                        from: bag.color().to_string(),
+                        to: color.clone(),
+                        count: *count,
+                    })
+            })
+            .collect::<Vec<_>>();
+        edges.sort();
+
+        Self {
+            nodes: nodes.into_iter().collect(),
+            edges,
+        }
+    }
+
+    pub fn write_dot<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        writeln!(writer, "digraph bags {{")?;
+
+        for node in &self.nodes {
+            writeln!(writer, "    {};", id(node))?;
+        }
+
+        for edge in &self.edges {
+            writeln!(
+                writer,
+                "    {} -> {} [label=\"{}\"];",
+                id(&edge.from),
+                id(&edge.to),
+                edge.count
+            )?;
+        }
+
+        writeln!(writer, "}}")?;
+
+        Ok(())
+    }
+
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), Error> {
+        serde_json::to_writer(writer, self)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Bags,
+        Edge,
+        Graph,
+        Scope,
+    };
+
+    const INPUT: &str = include_str!("input_example.txt");
+
+    fn edge(from: &str, to: &str, count: usize) -> Edge {
+        Edge {
+            from: from.into(),
+            to: to.into(),
+            count,
+        }
+    }
+
+    mod new {
+        use super::{
+            edge,
+            Bags,
+            Graph,
+            Scope,
+            INPUT,
+        };
+
+        #[test]
+        fn all() {
+            let got = Graph::new(&Bags::from(INPUT), Scope::All);
+
+            assert_eq!(9, got.nodes.len());
+            assert_eq!(13, got.edges.len());
+        }
+
+        #[test]
+        fn reachable_from() {
+            let expected = Graph {
+                nodes: vec![
+                    "dark olive".into(),
+                    "dotted black".into(),
+                    "faded blue".into(),
+                    "shiny gold".into(),
+                    "vibrant plum".into(),
+                ],
+                edges: vec![
+                    edge("dark olive", "dotted black", 4),
+                    edge("dark olive", "faded blue", 3),
+                    edge("shiny gold", "dark olive", 1),
+                    edge("shiny gold", "vibrant plum", 2),
+                    edge("vibrant plum", "dotted black", 6),
+                    edge("vibrant plum", "faded blue", 5),
+                ],
+            };
+            let got = Graph::new(&Bags::from(INPUT), Scope::ReachableFrom("shiny gold"));
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn reachable_to() {
+            let expected = Graph {
+                nodes: vec![
+                    "bright white".into(),
+                    "dark orange".into(),
+                    "light red".into(),
+                    "muted yellow".into(),
+                    "shiny gold".into(),
+                ],
+                edges: vec![
+                    edge("bright white", "shiny gold", 1),
+                    edge("dark orange", "bright white", 3),
+                    edge("dark orange", "muted yellow", 4),
+                    edge("light red", "bright white", 1),
+                    edge("light red", "muted yellow", 2),
+                    edge("muted yellow", "shiny gold", 2),
+                ],
+            };
+            let got = Graph::new(&Bags::from(INPUT), Scope::ReachableTo("shiny gold"));
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn cycle() {
+            const INPUT: &str = "shiny gold bags contain 1 muted blue bag.
+muted blue bags contain 2 shiny gold bags.
+dark red bags contain 1 shiny gold bag.";
+            let bags = Bags::from(INPUT);
+
+            let from = Graph::new(&bags, Scope::ReachableFrom("shiny gold"));
+            let to = Graph::new(&bags, Scope::ReachableTo("shiny gold"));
+
+            assert_eq!(vec!["muted blue", "shiny gold"], from.nodes);
+            assert_eq!(2, from.edges.len());
+            assert_eq!(vec!["dark red", "muted blue", "shiny gold"], to.nodes);
+            assert_eq!(3, to.edges.len());
+        }
+    }
+
+    mod write {
+        use super::{
+            edge,
+            Bags,
+            Graph,
+            Scope,
+        };
+
+        const INPUT: &str =
+            "shiny gold bags contain 2 muted blue bags.\nmuted blue bags contain no other bags.";
+
+        #[test]
+        fn dot() {
+            let expected = "digraph bags {\n    \"muted blue\";\n    \"shiny gold\";\n    \"shiny \
+                            gold\" -> \"muted blue\" [label=\"2\"];\n}\n";
+
+            let mut got = Vec::new();
+            Graph::new(&Bags::from(INPUT), Scope::All)
+                .write_dot(&mut got)
+                .expect("can not write dot");
+
+            assert_eq!(expected, String::from_utf8(got).unwrap());
+        }
+
+        #[test]
+        fn dot_escape() {
+            let graph = Graph {
+                nodes: vec!["café".into(), "pale \"gold\\".into()],
+                edges: vec![edge("pale \"gold\\", "café", 1)],
+            };
+            let expected = "digraph bags {\n    \"café\";\n    \"pale \\\"gold\\\\\";\n    \"pale \
+                            \\\"gold\\\\\" -> \"café\" [label=\"1\"];\n}\n";
+
+            let mut got = Vec::new();
+            graph.write_dot(&mut got).expect("can not write dot");
+
+            assert_eq!(expected, String::from_utf8(got).unwrap());
+        }
+
+        #[test]
+        fn json() {
+            let expected = r#"{"nodes":["muted blue","shiny gold"],"edges":[{"from":"shiny gold","to":"muted blue","count":2}]}"#;
+
+            let mut got = Vec::new();
+            Graph::new(&Bags::from(INPUT), Scope::All)
+                .write_json(&mut got)
+                .expect("can not write json");
+
+            assert_eq!(expected, String::from_utf8(got).unwrap());
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use rand::{
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng,
+    SeedableRng,
+};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    ops::RangeInclusive,
+};
+
+use super::bag::{
+    Bag,
+    Bags,
+};
+
+const MODIFIERS: [&str; 16] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "vibrant",
+];
+
+const COLORS: [&str; 16] = [
+    "aqua", "beige", "black", "blue", "bronze", "coral", "gold", "green", "indigo", "lime",
+    "olive", "orange", "plum", "red", "teal", "white",
+];
+
+/// Generates random bag rules. The same seed always produces the same rules.
+///
+/// Colors are spread evenly over `depth` levels and bags only contain bags of
+/// deeper levels, so the rules are acyclic unless `cycles` is set.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Generator {
+    pub seed: u64,
+
+    pub colors: usize,
+    pub branching: usize,
+    pub depth: usize,
+    pub counts: RangeInclusive<usize>,
+
+    /// Amount of rules that contain their own container.
+    pub cycles: usize,
+
+    /// Amount of rules that contain a color without its own rule.
+    pub dangling: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+
+            colors: 100,
+            branching: 3,
+            depth: 5,
+            counts: 1..=5,
+
+            cycles: 0,
+            dangling: 0,
+        }
+    }
+}
+
+impl Generator {
+    pub fn generate(&self) -> Bags {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let depth = self.depth.max(1);
+        let levels = (0..self.colors)
+            .map(|index| index * depth / self.colors)
+            .collect::<Vec<_>>();
+        let level_starts = (0..=depth + 1)
+            .map(|level| levels.partition_point(|other| *other < level))
+            .collect::<Vec<_>>();
+
+        // Rules reference colors by index until they are written out.
+        let mut rules = levels
+            .iter()
+            .map(|level| {
+                let mut can_contain = BTreeMap::new();
+
+                let deeper = level_starts[level + 1]..self.colors;
+                let next = level_starts[level + 1]..level_starts[level + 2];
+
+                // Always include a bag of the next level so the rules reach the full depth.
+                if self.branching > 0 && !next.is_empty() {
+                    can_contain.insert(rng.gen_range(next), rng.gen_range(self.counts.clone()));
+                }
+
+                while can_contain.len() < self.branching.min(deeper.len()) {
+                    can_contain.insert(
+                        rng.gen_range(deeper.clone()),
+                        rng.gen_range(self.counts.clone()),
+                    );
+                }
+
+                can_contain
+            })
+            .collect::<Vec<_>>();
+
+        let containers = (0..rules.len())
+            .filter(|index| !rules[*index].is_empty())
+            .collect::<Vec<_>>();
+
+        for _ in 0..self.cycles {
+            let container = match containers.choose(&mut rng) {
+                Some(container) => *container,
+                None => break,
+            };
+
+            let inner = *rules[container]
+                .keys()
+                .nth(rng.gen_range(0..rules[container].len()))
+                .expect("container without content");
+
+            rules[inner].insert(container, rng.gen_range(self.counts.clone()));
+        }
+
+        for missing in 0..self.dangling {
+            if rules.is_empty() {
+                break;
+            }
+
+            let index = rng.gen_range(0..rules.len());
+            rules[index].insert(self.colors + missing, rng.gen_range(self.counts.clone()));
+        }
+
+        rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, can_contain)| {
+                let can_contain = can_contain
+                    .into_iter()
+                    .map(|(other, count)| (color_name(other), count))
+                    .collect();
+
+                Bag::new(color_name(index), can_contain)
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write!(writer, "{}", self.generate())
+    }
+}
+
+/// Two word color name that is unique for every index.
+fn color_name(index: usize) -> String {
+    let modifier = MODIFIERS[index % MODIFIERS.len()];
+    let color = COLORS[(index / MODIFIERS.len()) % COLORS.len()];
+
+    match index / (MODIFIERS.len() * COLORS.len()) {
+        0 => format!("{} {}", modifier, color),
+        round => format!("{}{} {}", modifier, round, color),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Bag,
+        Generator,
+    };
+    use std::collections::HashSet;
+
+    #[test]
+    fn same_seed() {
+        let generator = Generator::default();
+
+        assert_eq!(generator.generate(), generator.generate());
+    }
+
+    #[test]
+    fn round_trip() {
+        let generator = Generator {
+            colors: 300,
+            ..Generator::default()
+        };
+
+        let mut written = Vec::new();
+        generator.write(&mut written).expect("can not write rules");
+        let written = String::from_utf8(written).unwrap();
+
+        assert_eq!(generator.generate(), super::Bags::from(written.as_str()));
+    }
+
+    #[test]
+    fn depth() {
+        let generator = Generator {
+            colors: 50,
+            branching: 2,
+            depth: 6,
+            counts: 1..=1,
+            ..Generator::default()
+        };
+        let bags = generator.generate();
+
+        let got = bags
+            .iter()
+            .filter_map(|bag| bags.longest_path(&super::color_name(0), bag.color()))
+            .map(|path| path.steps())
+            .max();
+
+        assert_eq!(Some(5), got);
+    }
+
+    #[test]
+    fn acyclic() {
+        let bags = Generator::default().generate();
+
+        for bag in bags.iter() {
+            assert!(!bags.find_all_containers(bag.color()).contains(bag.color()));
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let bags = Generator {
+            cycles: 1,
+            ..Generator::default()
+        }
+        .generate();
+
+        let got = bags.iter().any(|bag| {
+            bag.can_contain().keys().any(|contained| {
+                bags.iter()
+                    .find(|other| other.color() == contained)
+                    .is_some_and(|other| other.can_contain().contains_key(bag.color()))
+            })
+        });
+
+        assert!(got);
+    }
+
+    #[test]
+    fn dangling() {
+        let bags = Generator {
+            dangling: 3,
+            ..Generator::default()
+        }
+        .generate();
+
+        let defined = bags.iter().map(Bag::color).collect::<HashSet<_>>();
+        let got = bags
+            .iter()
+            .flat_map(|bag| bag.can_contain().keys())
+            .filter(|color| !defined.contains(color.as_str()))
+            .count();
+
+        assert_eq!(3, got);
+    }
+}
@@ -1,10 +1,16 @@
 use thiserror::Error;
 
 mod bag;
+#[allow(dead_code)]
+mod export;
+#[allow(dead_code)]
+mod generator;
+#[allow(dead_code)]
+mod path;
 
 use bag::Bags;
 
-#[allow(clippy::empty_enum)]
+#[allow(clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {}
 
@@ -18,6 +24,7 @@ pub fn run() -> Result<(), Error> {
     Ok(())
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_1() -> Result<usize, Error> {
     let count = Bags::from(include_str!("input.txt"))
         .find_all_containers("shiny gold")
@@ -26,6 +33,7 @@ pub fn part_1() -> Result<usize, Error> {
     Ok(count)
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_2() -> Result<usize, Error> {
     let count = Bags::from(include_str!("input.txt"))
         .must_contain("shiny gold")
@@ -48,7 +56,7 @@ mod test {
 
     #[test]
     fn part_2() {
-        let expected = 155802;
+        let expected = 155_802;
         let got = super::part_2().unwrap();
 
         assert_eq!(expected, got)
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use super::bag::{
+    Bag,
+    Bags,
+};
+
+/// One way `colors[0]` ends up inside the last color of `colors`.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Path {
+    pub colors: Vec<String>,
+
+    /// How many of the last bag this path puts into the first one, saturates
+    /// at `usize::MAX` on long chains.
+    pub multiplicity: usize,
+}
+
+impl Path {
+    /// Number of containment steps along the path.
+    pub fn steps(&self) -> usize {
+        self.colors.len() - 1
+    }
+}
+
+/// Depth first iterator over all containment paths between two colors. Only
+/// the current path is kept in memory. Colors already on the current path are
+/// skipped so cyclic rules terminate.
+#[derive(Debug)]
+pub struct Paths<'a> {
+    index: HashMap<&'a str, &'a Bag>,
+    to: String,
+
+    path: Vec<(&'a str, usize)>,
+    stack: Vec<std::vec::IntoIter<(&'a str, usize)>>,
+}
+
+impl<'a> Paths<'a> {
+    fn new(bags: &'a Bags, from: &'a str, to: &str) -> Self {
+        let index = bags.iter().map(|bag| (bag.color(), bag)).collect();
+
+        let mut paths = Self {
+            index,
+            to: to.to_string(),
+
+            path: Vec::new(),
+            stack: Vec::new(),
+        };
+
+        paths.push(from, 1);
+
+        paths
+    }
+
+    fn push(&mut self, color: &'a str, multiplicity: usize) {
+        let mut children = self.index.get(color).map_or_else(Vec::new, |bag| {
+            bag.can_contain()
+                .iter()
+                .map(|(color, count)| (color.as_str(), *count))
+                .collect()
+        });
+        children.sort_unstable();
+
+        self.path.push((color, multiplicity));
+        self.stack.push(children.into_iter());
+    }
+}
+
+impl Iterator for Paths<'_> {
+    type Item = Path;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some((color, count)) = self.stack.last_mut()?.next() else {
+                self.stack.pop();
+                self.path.pop();
+                continue;
+            };
+
+            if self.path.iter().any(|(visited, _)| *visited == color) {
+                continue;
+            }
+
+            let multiplicity = self
+                .path
+                .last()
+                .map_or(count, |(_, m)| m.saturating_mul(count));
+
+            if color == self.to {
+                let colors = self
+                    .path
+                    .iter()
+                    .map(|(color, _)| *color)
+                    .chain(std::iter::once(color))
+                    .map(ToString::to_string)
+                    .collect();
+
+                return Some(Path {
+                    colors,
+                    multiplicity,
+                });
+            }
+
+            self.push(color, multiplicity);
+        }
+    }
+}
+
+impl Bags {
+    /// All paths along which `to` ends up inside `from`.
+    pub fn paths<'a>(&'a self, from: &'a str, to: &str) -> Paths<'a> {
+        Paths::new(self, from, to)
+    }
+
+    pub fn shortest_path(&self, from: &str, to: &str) -> Option<Path> {
+        self.paths(from, to).min_by_key(Path::steps)
+    }
+
+    pub fn longest_path(&self, from: &str, to: &str) -> Option<Path> {
+        self.paths(from, to).max_by_key(Path::steps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Bags,
+        Path,
+    };
+
+    const INPUT: &str = include_str!("input_example.txt");
+
+    fn path(colors: &[&str], multiplicity: usize) -> Path {
+        Path {
+            colors: colors.iter().map(ToString::to_string).collect(),
+            multiplicity,
+        }
+    }
+
+    mod paths {
+        use super::{
+            path,
+            Bags,
+            INPUT,
+        };
+
+        #[test]
+        fn example() {
+            let expected = vec![
+                path(&["light red", "bright white", "shiny gold"], 1),
+                path(&["light red", "muted yellow", "shiny gold"], 4),
+            ];
+            let got = Bags::from(INPUT)
+                .paths("light red", "shiny gold")
+                .collect::<Vec<_>>();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn multiplicity_sums_to_must_contain() {
+            let bags = Bags::from(INPUT);
+
+            let expected = bags
+                .must_contain("shiny gold")
+                .into_iter()
+                .filter(|(color, _)| color == "faded blue")
+                .map(|(_, count)| count)
+                .sum::<usize>();
+            let got = bags
+                .paths("shiny gold", "faded blue")
+                .map(|path| path.multiplicity)
+                .sum::<usize>();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn no_path() {
+            let got = Bags::from(INPUT).paths("faded blue", "shiny gold").count();
+
+            assert_eq!(0, got);
+        }
+
+        #[test]
+        fn cycle() {
+            const INPUT: &str = "light red bags contain 1 muted blue bag.\nmuted blue bags \
+                                 contain 2 light red bags, 3 shiny gold bags.";
+
+            let expected = vec![path(&["light red", "muted blue", "shiny gold"], 3)];
+            let got = Bags::from(INPUT)
+                .paths("light red", "shiny gold")
+                .collect::<Vec<_>>();
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    mod saturate {
+        use crate::day07::generator::Generator;
+
+        #[test]
+        fn deep_chain() {
+            let bags = Generator {
+                colors: 100,
+                branching: 1,
+                depth: 100,
+                counts: 2..=2,
+                ..Generator::default()
+            }
+            .generate();
+
+            let got = bags.paths("bright aqua", "dim gold").collect::<Vec<_>>();
+
+            assert_eq!(1, got.len());
+            assert_eq!(99, got[0].steps());
+            assert_eq!(usize::MAX, got[0].multiplicity);
+        }
+    }
+
+    mod shortest_longest {
+        use super::{
+            path,
+            Bags,
+            INPUT,
+        };
+
+        #[test]
+        fn shortest() {
+            let expected = Some(path(&["dark orange", "bright white", "shiny gold"], 3));
+            let got = Bags::from(INPUT).shortest_path("dark orange", "shiny gold");
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn longest() {
+            let expected = Some(path(
+                &[
+                    "light red",
+                    "muted yellow",
+                    "shiny gold",
+                    "vibrant plum",
+                    "faded blue",
+                ],
+                40,
+            ));
+            let got = Bags::from(INPUT).longest_path("light red", "faded blue");
+
+            assert_eq!(expected, got);
+        }
+    }
+}
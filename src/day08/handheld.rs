@@ -126,9 +126,8 @@ mod test {
 
             let handlheld = INPUT.parse::<super::Handheld>().expect("invalid input");
             let got = match handlheld.run() {
-                Ok(h) => h.accumulator,
-                Err(super::Error::LoopFound(h)) => h.accumulator,
-                Err(err) => panic!(err),
+                Ok(h) | Err(super::Error::LoopFound(h)) => h.accumulator,
+                Err(err) => panic!("{}", err),
             };
 
             assert_eq!(expected, got);
@@ -202,7 +201,7 @@ pub mod fixer {
             let to_check = handheld
                 .instructions
                 .iter()
-                .cloned()
+                .copied()
                 .enumerate()
                 .filter(|(_, instruction)| instruction.is_jmp())
                 .collect();
@@ -319,8 +318,8 @@ mod instruction {
         /// Increase or decrease the accumulator of the handlheld by the isize.
         Acc(isize),
 
-        /// Update the instruction_pointer to a new value relative to the jmp
-        /// instruction_pointer by the given isize.
+        /// Update the `instruction_pointer` to a new value relative to the jmp
+        /// `instruction_pointer` by the given isize.
         Jmp(isize),
 
         /// Do nothing
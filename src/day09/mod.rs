@@ -4,7 +4,7 @@ mod xmas;
 
 use xmas::Xmas;
 
-#[allow(clippy::empty_enum)]
+#[allow(clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("found invalid number in input: {0}")]
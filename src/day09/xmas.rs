@@ -2,7 +2,7 @@ use itertools::Itertools;
 use rayon::prelude::*;
 use thiserror::Error;
 
-#[allow(clippy::empty_enum)]
+#[allow(dead_code, clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {}
 
@@ -14,12 +14,12 @@ pub struct Xmas {
 
 impl Xmas {
     pub fn new(input: Vec<usize>, previous_count: usize) -> Self {
-        let preamble = input.iter().take(previous_count).cloned().collect();
+        let preamble = input.iter().take(previous_count).copied().collect();
         let to_check = input.into_iter().skip(previous_count).collect();
 
         Self {
-            previous_count,
             preamble,
+            previous_count,
             to_check,
         }
     }
@@ -0,0 +1,371 @@
+use std::convert::TryFrom;
+use thiserror::Error;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum Error {
+    #[error("row {row} has {got} cells but expected {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("expected {expected} cells for the grid size but got {got}")]
+    InvalidSize { expected: usize, got: usize },
+}
+
+/// What a grid returns for positions outside of its cells.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub enum Edge<T> {
+    /// Columns repeat endlessly to the left and right, rows are bounded.
+    WrapHorizontal,
+
+    /// Columns and rows repeat endlessly.
+    Wrap,
+
+    /// There is nothing outside of the grid.
+    #[default]
+    Bounded,
+
+    /// Everything outside of the grid is the given value.
+    Infinite(T),
+}
+
+/// Offsets of the four orthogonal neighbours.
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours including the diagonals.
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// Two dimensional grid with row major storage. Positions are signed `(x, y)`
+/// tuples so callers can step over the edges and let the grid decide what is
+/// there.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+
+    edge: Edge<T>,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            cells: Vec::new(),
+            width: 0,
+            height: 0,
+
+            edge: Edge::default(),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>, edge: Edge<T>) -> Result<Self, Error> {
+        if cells.len() != width * height {
+            return Err(Error::InvalidSize {
+                expected: width * height,
+                got: cells.len(),
+            });
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+
+            edge,
+        })
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>, edge: Edge<T>) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (row, entries) in rows.into_iter().enumerate() {
+            if entries.len() != width {
+                return Err(Error::RaggedRow {
+                    row,
+                    expected: width,
+                    got: entries.len(),
+                });
+            }
+
+            cells.extend(entries);
+        }
+
+        Self::new(width, height, cells, edge)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn edge(&self) -> &Edge<T> {
+        &self.edge
+    }
+
+    /// Index into the cells for the position after applying the edge behavior.
+    /// Returns `None` if the position is outside of the grid.
+    fn index(&self, (x, y): (isize, isize)) -> Option<usize> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+
+        let width = isize::try_from(self.width).ok()?;
+        let height = isize::try_from(self.height).ok()?;
+
+        let (x, y) = match self.edge {
+            Edge::WrapHorizontal => (x.rem_euclid(width), y),
+            Edge::Wrap => (x.rem_euclid(width), y.rem_euclid(height)),
+            Edge::Bounded | Edge::Infinite(_) => (x, y),
+        };
+
+        let x = usize::try_from(x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|y| *y < self.height)?;
+
+        Some(y * self.width + x)
+    }
+
+    pub fn get(&self, position: (isize, isize)) -> Option<&T> {
+        match (self.index(position), &self.edge) {
+            (Some(index), _) => Some(&self.cells[index]),
+            (None, Edge::Infinite(default)) => Some(default),
+            (None, _) => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, position: (isize, isize)) -> Option<&mut T> {
+        let index = self.index(position)?;
+
+        Some(&mut self.cells[index])
+    }
+
+    /// Neighbours for the given offsets, skipping those outside of the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        (x, y): (isize, isize),
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((isize, isize), &'a T)> + 'a {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let position = (x + dx, y + dy);
+            self.get(position).map(|cell| (position, cell))
+        })
+    }
+
+    pub fn neighbours_4(
+        &self,
+        position: (isize, isize),
+    ) -> impl Iterator<Item = ((isize, isize), &T)> + '_ {
+        self.neighbours(position, &NEIGHBOURS_4)
+    }
+
+    pub fn neighbours_8(
+        &self,
+        position: (isize, isize),
+    ) -> impl Iterator<Item = ((isize, isize), &T)> + '_ {
+        self.neighbours(position, &NEIGHBOURS_8)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y >= self.height {
+            return None;
+        }
+
+        Some(&self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).filter_map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T> + '_> {
+        if x >= self.width {
+            return None;
+        }
+
+        Some(self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).filter_map(move |x| self.column(x))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Edge,
+        Error,
+        Grid,
+    };
+
+    fn grid(edge: Edge<u8>) -> Grid<u8> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]], edge).expect("invalid grid")
+    }
+
+    mod from_rows {
+        use super::{
+            Edge,
+            Error,
+            Grid,
+        };
+
+        #[test]
+        fn empty() {
+            let got = Grid::<u8>::from_rows(vec![], Edge::Bounded).expect("invalid grid");
+
+            assert_eq!(0, got.width());
+            assert_eq!(0, got.height());
+            assert_eq!(None, got.get((0, 0)));
+        }
+
+        #[test]
+        fn ragged() {
+            let expected = Err(Error::RaggedRow {
+                row: 1,
+                expected: 2,
+                got: 1,
+            });
+            let got = Grid::from_rows(vec![vec![1, 2], vec![3]], Edge::Bounded);
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn invalid_size() {
+            let expected = Err(Error::InvalidSize {
+                expected: 4,
+                got: 3,
+            });
+            let got = Grid::new(2, 2, vec![1, 2, 3], Edge::Bounded);
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    mod get {
+        use super::{
+            grid,
+            Edge,
+        };
+
+        #[test]
+        fn bounded() {
+            let grid = grid(Edge::Bounded);
+
+            assert_eq!(Some(&5), grid.get((1, 1)));
+            assert_eq!(None, grid.get((3, 0)));
+            assert_eq!(None, grid.get((-1, 0)));
+            assert_eq!(None, grid.get((0, 2)));
+        }
+
+        #[test]
+        fn wrap_horizontal() {
+            let grid = grid(Edge::WrapHorizontal);
+
+            assert_eq!(Some(&1), grid.get((3, 0)));
+            assert_eq!(Some(&6), grid.get((-1, 1)));
+            assert_eq!(None, grid.get((0, 2)));
+        }
+
+        #[test]
+        fn wrap() {
+            let grid = grid(Edge::Wrap);
+
+            assert_eq!(Some(&4), grid.get((3, 3)));
+            assert_eq!(Some(&3), grid.get((-1, -2)));
+        }
+
+        #[test]
+        fn infinite() {
+            let grid = grid(Edge::Infinite(0));
+
+            assert_eq!(Some(&2), grid.get((1, 0)));
+            assert_eq!(Some(&0), grid.get((7, -7)));
+        }
+    }
+
+    mod neighbours {
+        use super::{
+            grid,
+            Edge,
+        };
+
+        #[test]
+        fn four_bounded() {
+            let expected = vec![((1, 0), &2), ((0, 1), &4)];
+            let grid = grid(Edge::Bounded);
+            let got = grid.neighbours_4((0, 0)).collect::<Vec<_>>();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn eight_wrap_horizontal() {
+            let expected = vec![
+                ((1, 0), &2),
+                ((1, 1), &5),
+                ((0, 1), &4),
+                ((-1, 1), &6),
+                ((-1, 0), &3),
+            ];
+            let grid = grid(Edge::WrapHorizontal);
+            let got = grid.neighbours_8((0, 0)).collect::<Vec<_>>();
+
+            assert_eq!(expected, got);
+        }
+    }
+
+    mod views {
+        use super::{
+            grid,
+            Edge,
+        };
+
+        #[test]
+        fn rows() {
+            let expected = vec![&[1, 2, 3][..], &[4, 5, 6][..]];
+            let grid = grid(Edge::Bounded);
+            let got = grid.rows().collect::<Vec<_>>();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn column() {
+            let expected = vec![&2, &5];
+            let grid = grid(Edge::Bounded);
+            let got = grid.column(1).expect("missing column").collect::<Vec<_>>();
+
+            assert_eq!(expected, got);
+        }
+
+        #[test]
+        fn columns() {
+            let expected = vec![vec![&1, &4], vec![&2, &5], vec![&3, &6]];
+            let grid = grid(Edge::Bounded);
+            let got = grid
+                .columns()
+                .map(Iterator::collect)
+                .collect::<Vec<Vec<_>>>();
+
+            assert_eq!(expected, got);
+        }
+    }
+}
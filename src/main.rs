@@ -1,15 +1,22 @@
-#![feature(iterator_fold_self)]
 #![warn(clippy::pedantic)]
+// Newer clippy versions flag the code base's explicit `format!("{}", x)`
+// arguments and unit blocks without a trailing semicolon everywhere
+#![allow(clippy::uninlined_format_args, clippy::semicolon_if_nothing_returned)]
 //#![warn(clippy::unwrap_used)]
 #![warn(rust_2018_idioms, unused_lifetimes, missing_debug_implementations)]
 #![forbid(unsafe_code)]
-#![feature(test)]
+#![cfg_attr(test, feature(test))]
 #[cfg(test)]
 extern crate test;
 
 #[allow(dead_code)]
 mod template;
 
+#[allow(dead_code)]
+mod grid;
+
+mod csv;
+
 mod day01;
 mod day02;
 mod day03;
@@ -30,6 +37,7 @@ fn main() -> Result<(), Error> {
         match day.as_str() {
             "day01" => day01::run()?,
             "day02" => day02::run()?,
+            "day02-audit" => day02::audit::run(std::env::args().skip(2))?,
             "day03" => day03::run()?,
             "day04" => day04::run()?,
             "day05" => day05::run()?,
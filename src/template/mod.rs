@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[allow(clippy::empty_enum)]
+#[allow(clippy::empty_enums)]
 #[derive(Debug, Error)]
 pub enum Error {}
 
@@ -11,10 +11,12 @@ pub fn run() -> Result<(), Error> {
     Ok(())
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_1() -> Result<usize, Error> {
     Ok(0)
 }
 
+#[allow(clippy::unnecessary_wraps)]
 pub fn part_2() -> Result<usize, Error> {
     Ok(0)
 }